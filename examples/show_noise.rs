@@ -22,7 +22,7 @@ use noiz::{
         grid::GridNoise,
         merging::{
             EuclideanDistance,
-            ManhatanDistance,
+            ManhattanDistance,
         },
         noise_op,
         norm::{
@@ -47,8 +47,8 @@ use noiz::{
             ExactDistanceToEdge,
             RelativeDistanceToEdge,
             Voronoi,
-            Worly,
-            worly_mode,
+            Worley,
+            worley_mode,
         },
     },
     spatial::interpolating::Cubic,
@@ -90,7 +90,7 @@ fn main() -> AppExit {
         .run()
 }
 
-type NoiseUsed = WorlyNoise;
+type NoiseUsed = WorleyNoise;
 
 fn make_noise(image: &mut Image) {
     let width = image.width();
@@ -172,7 +172,7 @@ noise_op! {
     pub struct CellularNoise for Vec2 -> UNorm = SpatialNoiseSettings
     impl
     fn GridNoise = args.period.into();
-    fn Voronoi<2, Cellular<ManhatanDistance>, true> = Voronoi::new_default(1.0.adapt(), args.rand_32());
+    fn Voronoi<2, Cellular<ManhattanDistance>, true> = Voronoi::new_default(1.0.adapt(), args.rand_32());
     fn SeedOf;
     as UNorm
 }
@@ -194,10 +194,10 @@ noise_op! {
 }
 
 noise_op! {
-    pub struct WorlyNoise for Vec2 -> UNorm = SpatialNoiseSettings
+    pub struct WorleyNoise for Vec2 -> UNorm = SpatialNoiseSettings
     impl
     fn GridNoise = args.period.into();
-    fn Voronoi<2, Worly<EuclideanDistance, worly_mode::Nearest>, false> = Voronoi::new(1.0, args.rand_32(), Worly::shrunk_by(1.0));
+    fn Voronoi<2, Worley<EuclideanDistance, worley_mode::Nearest>, false> = Voronoi::new(1.0, args.rand_32(), Worley::shrunk_by(1.0));
     || input.inverse();
 }
 
@@ -8,8 +8,10 @@ use quote::{
 use syn::{
     Attribute,
     Expr,
+    ExprIf,
     Field,
     FieldMutability,
+    Generics,
     Ident,
     LitInt,
     Local,
@@ -25,7 +27,6 @@ use syn::{
         ParseStream,
         discouraged::Speculative,
     },
-    parse_macro_input,
     parse_quote,
     punctuated::Punctuated,
     token::{
@@ -50,10 +51,12 @@ impl Parse for NoiseDefinition {
             let visibility = input.parse()?;
             _ = input.parse::<Token![struct]>()?;
             let name = input.parse()?;
+            let generics = input.parse()?;
             FullStruct {
                 name,
                 visibility,
                 attributes,
+                generics,
                 data: Default::default(),
             }
         };
@@ -62,6 +65,7 @@ impl Parse for NoiseDefinition {
         let input_types = input.parse()?;
         _ = input.parse::<Token![->]>()?;
         let output = input.parse()?;
+        noise.generics.where_clause = input.parse()?;
 
         _ = input.parse::<Token![=]>()?;
         let source = input.parse()?;
@@ -82,8 +86,8 @@ impl Parse for NoiseDefinition {
     }
 }
 
-impl ToTokens for NoiseDefinition {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+impl NoiseDefinition {
+    fn expand(&self) -> Result<proc_macro2::TokenStream> {
         let NoiseDefinition {
             noise,
             input,
@@ -95,21 +99,27 @@ impl ToTokens for NoiseDefinition {
             .iter()
             .map(|op| op.quote_construction(&noise.name));
         let noise_name = &noise.name;
-        let noise_fields = noise.filed_names().into_iter().collect::<Vec<_>>();
+        let noise_fields = noise.field_names()?;
 
         let mut noise_impl = Vec::new();
         for op in operations.iter() {
             noise_impl.push(op.quote_noise());
         }
 
-        let source = source.quote_source(noise_name, creation, noise_fields.iter().copied());
+        let source = source.quote_source(
+            noise_name,
+            &noise.generics,
+            creation,
+            noise_fields.iter().copied(),
+        )?;
+        let (impl_generics, ty_generics, where_clause) = noise.generics.split_for_impl();
 
-        tokens.extend(quote! {
+        Ok(quote! {
             #noise
 
             #source
 
-            impl noiz::noise::NoiseOp<#input> for #noise_name {
+            impl #impl_generics noiz::noise::NoiseOp<#input> for #noise_name #ty_generics #where_clause {
                 type Output = #output;
 
                 fn get(&self, input: #input) -> Self::Output{
@@ -123,10 +133,10 @@ impl ToTokens for NoiseDefinition {
                 }
             }
 
-            impl noiz::noise::Noise for #noise_name {
+            impl #impl_generics noiz::noise::Noise for #noise_name #ty_generics #where_clause {
                 type Input = #input;
             }
-        });
+        })
     }
 }
 
@@ -140,18 +150,21 @@ impl NoiseSource {
     fn quote_source<'b>(
         &self,
         noise_name: &Ident,
+        generics: &Generics,
         creation: impl Iterator<Item = proc_macro2::TokenStream>,
         noise_fields: impl Iterator<Item = &'b Ident>,
-    ) -> proc_macro2::TokenStream {
-        match self {
+    ) -> Result<proc_macro2::TokenStream> {
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        Ok(match self {
             NoiseSource::Custom(args) => {
                 let args_name = &args.name;
-                let args_fields = args.filed_names().into_iter().collect::<Vec<_>>();
-                let args_params = args.filed_names_and_types();
+                let (_, args_ty_generics, _) = args.generics.split_for_impl();
+                let args_fields = args.field_names()?;
+                let args_params = args.field_names_and_types()?;
                 quote! {
                     #args
 
-                    impl #noise_name  {
+                    impl #impl_generics #noise_name #ty_generics #where_clause {
                         pub fn new(#args_params) -> Self {
                             #(#creation)*
 
@@ -161,8 +174,8 @@ impl NoiseSource {
                         }
                     }
 
-                    impl From<#args_name> for #noise_name {
-                        fn from(value: #args_name) -> Self {
+                    impl #impl_generics From<#args_name #args_ty_generics> for #noise_name #ty_generics #where_clause {
+                        fn from(value: #args_name #args_ty_generics) -> Self {
                             let #args_name {
                                 #(#args_fields,)*
                             } = value;
@@ -174,7 +187,7 @@ impl NoiseSource {
             NoiseSource::Existing(existing) => {
                 quote! {
 
-                    impl #noise_name  {
+                    impl #impl_generics #noise_name #ty_generics #where_clause {
                         pub fn new(mut args: #existing) -> Self {
                             #(#creation)*
 
@@ -184,7 +197,7 @@ impl NoiseSource {
                         }
                     }
 
-                    impl From<#existing> for #noise_name {
+                    impl #impl_generics From<#existing> for #noise_name #ty_generics #where_clause {
                         fn from(value: #existing) -> Self {
                             Self::new(value)
                         }
@@ -194,7 +207,7 @@ impl NoiseSource {
             NoiseSource::RawParams(params) => {
                 let params = params.iter();
                 quote! {
-                    impl #noise_name  {
+                    impl #impl_generics #noise_name #ty_generics #where_clause {
                         pub fn new(#(mut #params),*) -> Self {
                             #(#creation)*
 
@@ -205,7 +218,7 @@ impl NoiseSource {
                     }
                 }
             }
-        }
+        })
     }
 }
 
@@ -220,16 +233,23 @@ impl Parse for NoiseSource {
             return Ok(Self::RawParams(params));
         }
 
-        if let Ok(custom) = input.parse::<FullStruct>() {
-            Ok(Self::Custom(custom))
-        } else if let Ok(existing) = input.parse::<Type>() {
-            Ok(Self::Existing(existing))
-        } else {
-            panic!(
-                "Unexpected noise source. Must be a non-generic struct declaration, parameter \
-                 names in braces, or the name of another type."
-            );
+        // once it looks like a struct, errors in it are more helpful than a generic one.
+        let fork = input.fork();
+        _ = Attribute::parse_outer(&fork)?;
+        _ = fork.parse::<Visibility>()?;
+        if fork.peek(Token![struct]) {
+            return Ok(Self::Custom(input.parse()?));
+        }
+
+        let fork = input.fork();
+        if let Ok(existing) = fork.parse::<Type>() {
+            input.advance_to(&fork);
+            return Ok(Self::Existing(existing));
         }
+        Err(input.error(
+            "Unexpected noise source. Must be a struct declaration, parameter names in braces, or \
+             the name of another type.",
+        ))
     }
 }
 
@@ -237,39 +257,64 @@ struct FullStruct {
     name: Ident,
     visibility: Visibility,
     attributes: Vec<Attribute>,
+    generics: Generics,
     data: Punctuated<Field, Token![,]>,
 }
 
 impl FullStruct {
-    fn filed_names(&self) -> impl IntoIterator<Item = &Ident> {
-        self.data
+    fn field_names(&self) -> Result<Vec<&Ident>> {
+        self.data.iter().map(field_name).collect()
+    }
+
+    fn field_names_and_types(&self) -> Result<proc_macro2::TokenStream> {
+        let params = self
+            .data
             .iter()
-            .map(|field| field.ident.as_ref().expect("Fields must be named."))
+            .map(|field| {
+                let name = field_name(field)?;
+                let ty = &field.ty;
+                Ok(quote! {mut #name: #ty})
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(quote! { #(#params),* })
     }
+}
 
-    fn filed_names_and_types(&self) -> proc_macro2::TokenStream {
-        let params = self.data.iter().map(|field| {
-            let name = field.ident.as_ref().expect("Fields must be named.");
-            let ty = &field.ty;
-            quote! {mut #name: #ty}
-        });
-        quote! { #(#params),* }
+/// Parses a named field, falling back to an unnamed one so [`field_name`] can report it.
+fn parse_field(input: ParseStream) -> Result<Field> {
+    let fork = input.fork();
+    _ = Attribute::parse_outer(&fork)?;
+    _ = fork.parse::<Visibility>()?;
+    if fork.peek(Ident) && fork.peek2(Token![:]) {
+        Field::parse_named(input)
+    } else {
+        Field::parse_unnamed(input)
     }
 }
 
+fn field_name(field: &Field) -> Result<&Ident> {
+    field
+        .ident
+        .as_ref()
+        .ok_or_else(|| syn::Error::new_spanned(field, "Fields must be named."))
+}
+
 impl Parse for FullStruct {
     fn parse(input: ParseStream) -> Result<Self> {
         let attributes = Attribute::parse_outer(input)?;
         let visibility = input.parse()?;
         _ = input.parse::<Token![struct]>()?;
         let name = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+        generics.where_clause = input.parse()?;
         let fields;
         braced!(fields in input);
-        let data = Punctuated::parse_terminated_with(&fields, |input| Field::parse_named(input))?;
+        let data = Punctuated::parse_terminated_with(&fields, parse_field)?;
         Ok(Self {
             name,
             visibility,
             attributes,
+            generics,
             data,
         })
     }
@@ -281,12 +326,14 @@ impl ToTokens for FullStruct {
             name,
             visibility,
             attributes,
+            generics,
             data,
         } = self;
         let data = data.iter();
+        let where_clause = &generics.where_clause;
         tokens.extend(quote! {
             #(#attributes)*
-            #visibility struct #name {
+            #visibility struct #name #generics #where_clause {
                 #(#data,)*
             }
         });
@@ -437,6 +484,78 @@ impl RefOp {
     }
 }
 
+#[derive(Clone)]
+struct MatchArm {
+    condition: Option<Expr>,
+    ops: Vec<Operation>,
+}
+
+#[derive(Clone)]
+struct MatchOp {
+    input_name: Ident,
+    input_type: Option<Type>,
+    arms: Vec<MatchArm>,
+}
+
+impl MatchOp {
+    fn parse(input: ParseStream, noise_amount: &mut u32) -> Result<Self> {
+        _ = input.parse::<Token![match]>()?;
+        _ = input.parse::<Token![ | ]>()?;
+        let (input_name, input_type) = if !input.peek(Token![ | ]) {
+            let input_name = input.parse()?;
+            let input_type = if input.parse::<Token![:]>().is_ok() {
+                Some(input.parse::<Type>()?)
+            } else {
+                None
+            };
+            (input_name, input_type)
+        } else {
+            (Ident::new("input", input.span()), None)
+        };
+        _ = input.parse::<Token![ | ]>()?;
+
+        let arms_stream;
+        let _ = braced!(arms_stream in input);
+        let mut arms = Vec::new();
+        while !arms_stream.is_empty() {
+            if arms
+                .last()
+                .is_some_and(|arm: &MatchArm| arm.condition.is_none())
+            {
+                return Err(arms_stream.error("The '_' arm of a 'match' must be the last one."));
+            }
+
+            let condition = if arms_stream.parse::<Token![_]>().is_ok() {
+                None
+            } else {
+                Some(Expr::parse_without_eager_brace(&arms_stream)?)
+            };
+            _ = arms_stream.parse::<Token![=>]>()?;
+
+            let is_block = arms_stream.peek(Brace);
+            let ops = if is_block {
+                let inner;
+                _ = braced!(inner in arms_stream);
+                Operation::parse_many(&inner, noise_amount)?
+            } else {
+                vec![Operation::parse(&arms_stream, noise_amount)?]
+            };
+            arms.push(MatchArm { condition, ops });
+
+            // like in rust, arms in braces don't need a comma.
+            if arms_stream.parse::<Token![,]>().is_err() && !is_block {
+                break;
+            }
+        }
+
+        Ok(Self {
+            input_name,
+            input_type,
+            arms,
+        })
+    }
+}
+
 #[derive(Clone)]
 enum Operation {
     Data(ConstructableField<Token![use]>),
@@ -446,9 +565,11 @@ enum Operation {
     Hold(Local),
     Parallel(Box<Operation>),
     ConstructionVariable(Local),
+    ConstructionIf(ExprIf),
     Mapping(Mapping),
     Fbm(FbmOp),
     RefOp(RefOp),
+    Match(MatchOp),
 }
 
 impl Operation {
@@ -475,7 +596,10 @@ impl Operation {
             | Operation::RefOp(_)
             | Operation::Convert(_)
             | Operation::Data(_) => true,
-            Operation::ConstructionVariable(_) | Operation::Hold(_) => false,
+            Operation::ConstructionVariable(_)
+            | Operation::ConstructionIf(_)
+            | Operation::Hold(_)
+            | Operation::Match(_) => false,
             Operation::Morph(morph) => !matches!(&morph.block, Expr::Block(_) | Expr::TryBlock(_)),
             Operation::Parallel(op) => op.needs_following_semi_colon(),
             Operation::Mapping(mapping) => mapping.operation.needs_following_semi_colon(),
@@ -505,6 +629,12 @@ impl Operation {
             Operation::Parallel(op) => op.store_fields(fields),
             Operation::Mapping(mapping) => mapping.operation.store_fields(fields),
             Operation::RefOp(ref_op) => ref_op.ops.iter().for_each(|op| op.store_fields(fields)),
+            // every arm is constructed, even though only one runs per sample.
+            Operation::Match(match_op) => match_op
+                .arms
+                .iter()
+                .flat_map(|arm| arm.ops.iter())
+                .for_each(|op| op.store_fields(fields)),
             _ => {}
         }
     }
@@ -565,19 +695,30 @@ impl Operation {
                 }
             }
             Operation::ConstructionVariable(binding) => binding.to_token_stream(),
+            Operation::ConstructionIf(branch) => branch.to_token_stream(),
             Operation::Parallel(op) => op.quote_construction(root_name),
             Operation::Mapping(mapping) => mapping.operation.quote_construction(root_name),
             Operation::RefOp(ref_op) => {
                 let ops = ref_op.ops.iter().map(|op| op.quote_construction(root_name));
                 quote! {#(#ops)*}
             }
+            Operation::Match(match_op) => {
+                let ops = match_op
+                    .arms
+                    .iter()
+                    .flat_map(|arm| arm.ops.iter())
+                    .map(|op| op.quote_construction(root_name));
+                quote! {#(#ops)*}
+            }
             _ => quote! {},
         }
     }
 
     fn quote_noise(&self) -> proc_macro2::TokenStream {
         match self {
-            Operation::Data(_) | Operation::ConstructionVariable(_) => {
+            Operation::Data(_)
+            | Operation::ConstructionVariable(_)
+            | Operation::ConstructionIf(_) => {
                 quote! {}
             }
             Operation::Noise(field) => {
@@ -702,22 +843,67 @@ impl Operation {
                     };
                 }
             }
+            Operation::Match(MatchOp {
+                input_name,
+                input_type,
+                arms,
+            }) => {
+                let binding = match input_type {
+                    Some(ty) => quote! {let #input_name: &#ty = &input;},
+                    None => quote! {let #input_name = &input;},
+                };
+
+                let mut branches = quote! {};
+                let mut has_default = false;
+                for MatchArm { condition, ops } in arms {
+                    let ops = ops.iter().map(|op| op.quote_noise());
+                    let body = quote! {
+                        {
+                            #(#ops)*
+                            input
+                        }
+                    };
+                    match condition {
+                        Some(condition) => branches.extend(quote! {if #condition #body else}),
+                        None => {
+                            branches.extend(body);
+                            has_default = true;
+                        }
+                    }
+                }
+                // without a '_' arm, unmatched inputs pass through unchanged.
+                if !has_default {
+                    branches.extend(quote! {{ input }});
+                }
+
+                quote! {
+                    let input = {
+                        #[allow(unused)]
+                        #binding
+                        #branches
+                    };
+                }
+            }
         }
     }
 
     fn parse(input: ParseStream, noise_amount: &mut u32) -> Result<Self> {
         *noise_amount += 1;
         if let Ok(_is_construction_variable) = input.parse::<Token![const]>() {
+            if input.peek(Token![if]) {
+                return Ok(Self::ConstructionIf(input.parse()?));
+            }
             match input.parse::<Stmt>() {
                 Ok(Stmt::Local(var)) => Ok(Self::ConstructionVariable(var)),
-                Ok(_) => {
-                    Err(input
-                        .error("Only local bindings are allowed to follow 'const' in a noise_op."))
-                }
+                Ok(_) => Err(input.error(
+                    "Only local bindings or 'if' are allowed to follow 'const' in a noise_op.",
+                )),
                 Err(err) => Err(err),
             }
         } else if input.peek(Token![ref]) {
             Ok(Self::RefOp(RefOp::parse(input, noise_amount)?))
+        } else if input.peek(Token![match]) {
+            Ok(Self::Match(MatchOp::parse(input, noise_amount)?))
         } else if let Ok(op) = ConstructableField::<Token![use]>::parse(input, noise_amount) {
             Ok(Self::Data(op))
         } else if let Ok(op) = ConstructableField::<Token![fn]>::parse(input, noise_amount) {
@@ -741,7 +927,7 @@ impl Operation {
         } else {
             Err(input.error(
                 "Unable to parse a noise operation. Expected a noise key word like 'let', '||', \
-                 'as', 'use', 'for', 'fn', 'loop', 'ref', 'mut, or 'const'.",
+                 'as', 'use', 'for', 'fn', 'loop', 'ref', 'match', 'mut, or 'const'.",
             ))
         }
     }
@@ -885,6 +1071,11 @@ impl Parse for Morph {
 
 #[proc_macro]
 pub fn noise_op(input: TokenStream) -> TokenStream {
-    let noise = parse_macro_input!(input as NoiseDefinition);
-    quote! {#noise}.into()
+    expand(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    syn::parse2::<NoiseDefinition>(input)?.expand()
 }
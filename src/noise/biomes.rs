@@ -0,0 +1,139 @@
+//! This module classifies noise values into biomes.
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    norm::UNorm,
+};
+
+/// The biome ids used by the default table of [`WhittakerBiome`].
+pub mod biome_ids {
+    /// Bare, burnt rock at high elevations with little moisture.
+    pub const SCORCHED: u32 = 0;
+    /// Bare rock at high elevations.
+    pub const BARE: u32 = 1;
+    /// Cold, treeless plains.
+    pub const TUNDRA: u32 = 2;
+    /// Snow covered peaks.
+    pub const SNOW: u32 = 3;
+    /// Dry, cool desert.
+    pub const TEMPERATE_DESERT: u32 = 4;
+    /// Low, scrubby plants.
+    pub const SHRUBLAND: u32 = 5;
+    /// Cold, coniferous forest.
+    pub const TAIGA: u32 = 6;
+    /// Open grassy plains.
+    pub const GRASSLAND: u32 = 7;
+    /// Forest that changes with the seasons.
+    pub const TEMPERATE_DECIDUOUS_FOREST: u32 = 8;
+    /// Wet, cool forest.
+    pub const TEMPERATE_RAIN_FOREST: u32 = 9;
+    /// Hot, dry desert.
+    pub const SUBTROPICAL_DESERT: u32 = 10;
+    /// Warm forest with a dry season.
+    pub const TROPICAL_SEASONAL_FOREST: u32 = 11;
+    /// Hot, wet forest.
+    pub const TROPICAL_RAIN_FOREST: u32 = 12;
+}
+
+/// A [`NoiseOp`] that classifies an (elevation, moisture) pair into a biome id by looking it up in
+/// a table. The table is indexed by elevation zone, from low to high, and then by moisture zone,
+/// from dry to wet. The default table is a 4 by 6 whittaker diagram using [`biome_ids`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WhittakerBiome<const ELEVATIONS: usize = 4, const MOISTURES: usize = 6> {
+    table: [[u32; MOISTURES]; ELEVATIONS],
+}
+
+impl Default for WhittakerBiome {
+    fn default() -> Self {
+        use biome_ids::{
+            BARE,
+            GRASSLAND,
+            SCORCHED,
+            SHRUBLAND,
+            SNOW,
+            SUBTROPICAL_DESERT,
+            TAIGA,
+            TEMPERATE_DECIDUOUS_FOREST,
+            TEMPERATE_DESERT,
+            TEMPERATE_RAIN_FOREST,
+            TROPICAL_RAIN_FOREST,
+            TROPICAL_SEASONAL_FOREST,
+            TUNDRA,
+        };
+        Self::new([
+            [
+                SUBTROPICAL_DESERT,
+                GRASSLAND,
+                TROPICAL_SEASONAL_FOREST,
+                TROPICAL_SEASONAL_FOREST,
+                TROPICAL_RAIN_FOREST,
+                TROPICAL_RAIN_FOREST,
+            ],
+            [
+                TEMPERATE_DESERT,
+                GRASSLAND,
+                GRASSLAND,
+                TEMPERATE_DECIDUOUS_FOREST,
+                TEMPERATE_DECIDUOUS_FOREST,
+                TEMPERATE_RAIN_FOREST,
+            ],
+            [
+                TEMPERATE_DESERT,
+                TEMPERATE_DESERT,
+                SHRUBLAND,
+                SHRUBLAND,
+                TAIGA,
+                TAIGA,
+            ],
+            [SCORCHED, BARE, TUNDRA, SNOW, SNOW, SNOW],
+        ])
+    }
+}
+
+impl<const ELEVATIONS: usize, const MOISTURES: usize> WhittakerBiome<ELEVATIONS, MOISTURES> {
+    /// Constructs a new [`WhittakerBiome`] from a custom table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty.
+    pub fn new(table: [[u32; MOISTURES]; ELEVATIONS]) -> Self {
+        assert!(
+            ELEVATIONS > 0 && MOISTURES > 0,
+            "A biome table must not be empty."
+        );
+        Self { table }
+    }
+}
+
+impl<const ELEVATIONS: usize, const MOISTURES: usize> NoiseOp<(UNorm, UNorm)>
+    for WhittakerBiome<ELEVATIONS, MOISTURES>
+{
+    type Output = u32;
+
+    #[inline]
+    fn get(&self, input: (UNorm, UNorm)) -> Self::Output {
+        let zone = |value: UNorm, zones: usize| {
+            ((value.adapt::<f32>() * zones as f32) as usize).min(zones - 1)
+        };
+        self.table[zone(input.0, ELEVATIONS)][zone(input.1, MOISTURES)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dry_peaks_are_scorched() {
+        let biomes = WhittakerBiome::default();
+        let high = UNorm::new_clamped(0.95);
+        let low = UNorm::new_clamped(0.05);
+        assert_eq!(biomes.get((high, low)), biome_ids::SCORCHED);
+        assert_eq!(biomes.get((low, high)), biome_ids::TROPICAL_RAIN_FOREST);
+        assert_eq!(
+            biomes.get((UNorm::new_clamped(1.0), UNorm::new_clamped(1.0))),
+            biome_ids::SNOW
+        );
+    }
+}
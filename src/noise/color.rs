@@ -0,0 +1,80 @@
+//! This module allows noise to be turned directly into colors.
+
+use bevy_color::{
+    LinearRgba,
+    Mix,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    conversions::NoiseConverter,
+    norm::UNorm,
+};
+
+impl NoiseType for LinearRgba {}
+
+/// A [`NoiseOp`] that samples `N` as a [`UNorm`] and maps it onto a color ramp from
+/// [`low`](Self::low) to [`high`](Self::high).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToColor<N> {
+    /// The noise to color.
+    pub noise: N,
+    /// The color of 0.
+    pub low: LinearRgba,
+    /// The color of 1.
+    pub high: LinearRgba,
+}
+
+impl<N> ToColor<N> {
+    /// Creates a [`ToColor`] that maps `noise` to grayscale, from black to white.
+    pub fn grayscale(noise: N) -> Self {
+        Self::ramp(noise, LinearRgba::BLACK, LinearRgba::WHITE)
+    }
+
+    /// Creates a [`ToColor`] that maps `noise` from `low` to `high`.
+    pub fn ramp(noise: N, low: LinearRgba, high: LinearRgba) -> Self {
+        Self { noise, low, high }
+    }
+}
+
+impl<I, N: NoiseOp<I>> NoiseOp<I> for ToColor<N>
+where
+    N::Output: NoiseConverter<UNorm, Input = N::Output>,
+{
+    type Output = LinearRgba;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        let t = self.noise.get(input).adapt::<UNorm>().adapt::<f32>();
+        self.low.mix(&self.high, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Identity;
+
+    impl NoiseOp<UNorm> for Identity {
+        type Output = UNorm;
+
+        fn get(&self, input: UNorm) -> Self::Output {
+            input
+        }
+    }
+
+    #[test]
+    fn grayscale_spans_black_to_white() {
+        let color = ToColor::grayscale(Identity);
+        let black = color.get(UNorm::new_clamped(0.0));
+        for channel in [black.red, black.green, black.blue] {
+            assert!(channel < 0.01);
+        }
+        let white = color.get(UNorm::new_clamped(1.0));
+        for channel in [white.red, white.green, white.blue] {
+            assert!(channel > 0.99);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! This module allows noise to be chosen and combined at runtime, like from a config file.
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    merging::Merger,
+};
+
+/// A [`NoiseOp`] stored behind a pointer, so that the noise can be chosen at runtime.
+/// Every [`BoxedNoise`] with the same input `I` and output `O` has the same type.
+pub struct BoxedNoise<I, O>(pub Box<dyn NoiseOp<I, Output = O>>);
+
+impl<I, O> BoxedNoise<I, O> {
+    /// Boxes this `noise`.
+    pub fn new(noise: impl NoiseOp<I, Output = O> + 'static) -> Self {
+        Self(Box::new(noise))
+    }
+}
+
+impl<I, O: NoiseType> NoiseOp<I> for BoxedNoise<I, O> {
+    type Output = O;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        self.0.get(input)
+    }
+}
+
+/// A [`NoiseOp`] that samples any number of [`BoxedNoise`] layers at the same input and merges
+/// them with [`Merger`] `M`. This allows a whole stack of noise to be assembled at runtime.
+pub struct LayerStack<I, O, M> {
+    /// The layers of noise to sample.
+    pub layers: Vec<BoxedNoise<I, O>>,
+    /// The [`Merger`] that combines the layers.
+    pub merger: M,
+}
+
+impl<I: Copy, O: NoiseType, M: Merger<O, ()>> NoiseOp<I> for LayerStack<I, O, M> {
+    type Output = M::Output;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        self.merger
+            .merge(self.layers.iter().map(|layer| layer.get(input)), &())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::*;
+    use crate::noise::merging::Total;
+
+    struct Scaled(f32);
+
+    impl NoiseOp<Vec2> for Scaled {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            input.x * self.0 + input.y
+        }
+    }
+
+    #[test]
+    fn layers_are_summed() {
+        let stack = LayerStack {
+            layers: vec![
+                BoxedNoise::new(Scaled(1.0)),
+                BoxedNoise::new(Scaled(-3.0)),
+                BoxedNoise::new(Scaled(0.5)),
+            ],
+            merger: Total,
+        };
+        for input in [Vec2::ZERO, Vec2::new(2.0, -1.0), Vec2::new(-0.5, 7.0)] {
+            let manual = Scaled(1.0).get(input) + Scaled(-3.0).get(input) + Scaled(0.5).get(input);
+            assert!((stack.get(input) - manual).abs() < 1e-6);
+        }
+    }
+}
@@ -1,10 +1,26 @@
 //! This module allows factional brownian motion (fbm) noise.
+//!
+//! In `noise_op!`, each group of octaves in a `loop` has its own operations, so different octaves
+//! can already run different kinds of noise. For example, the low octaves can be value noise while
+//! the high octaves are perlin noise. [`MixedFbm2`] does the same without the macro.
+
+use std::ops::Add;
+
+use bevy_math::{
+    Vec2,
+    Vec3,
+    Vec4,
+};
 
 use super::{
+    NoiseOp,
     NoiseType,
     Period,
     conversions::NoiseConverter,
-    norm::UNorm,
+    norm::{
+        SNorm,
+        UNorm,
+    },
 };
 
 /// Represents the settings of a fbm.
@@ -100,6 +116,8 @@ impl Octave<UncheckedFbm> for () {
 }
 
 /// Traditional fbm settings.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct StandardFbm {
     /// The period of the next octave.
     pub next_period: f32,
@@ -151,6 +169,8 @@ impl StandardFbm {
 
 /// An octave defined by a period and a weight.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct StandardOctave {
     /// The period of the octave.
     pub period: Period,
@@ -161,6 +181,7 @@ pub struct StandardOctave {
 
 /// Stores the final, normalized contribution of a [`Weighted`] octave.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WeightedOctave(pub UNorm);
 
 impl Octave<StandardFbm> for StandardOctave {
@@ -270,3 +291,507 @@ impl_weighted_accumulator!(
     OctaveProductAccumulator(1.0),
     mul
 );
+
+/// A [`PreAccumulator`] for billow noise. Each octave is converted to [`SNorm`] and split to its
+/// magnitude with [`SNorm::split_to_unorm`] before being weighted and summed, producing puffy,
+/// cloud-like features. The result is in [`UNorm`] range. See also [`TurbulenceOctaveSum`].
+pub struct OctaveBillow;
+
+/// A [`PreAccumulator`] for turbulence. This is the same sum of magnitudes as [`OctaveBillow`],
+/// which produces the classic turbulence field used for marble and fire.
+pub struct TurbulenceOctaveSum;
+
+/// The [`Accumulator`] for [`OctaveBillow`] and [`TurbulenceOctaveSum`].
+pub struct OctaveBillowAccumulator(pub f32);
+
+impl PostAccumulator for OctaveBillowAccumulator {
+    type Final = UNorm;
+
+    #[inline]
+    fn finish(self) -> Self::Final {
+        UNorm::new_clamped(self.0)
+    }
+}
+
+impl<T: NoiseConverter<SNorm, Input = T>> Accumulator<T, WeightedOctave>
+    for OctaveBillowAccumulator
+{
+    #[inline]
+    fn accumulate(&mut self, octave_result: T, octave: &WeightedOctave) {
+        let magnitude = T::convert(octave_result).split_to_unorm();
+        self.0 += (magnitude * octave.0).adapt::<f32>();
+    }
+}
+
+impl<const N: usize, T: NoiseConverter<SNorm, Input = T>> PreAccumulator<T, WeightedOctave, N>
+    for OctaveBillow
+{
+    type Accumulator = OctaveBillowAccumulator;
+
+    #[inline]
+    fn start_accumulate(self, octave_result: T, octave: &WeightedOctave) -> Self::Accumulator {
+        let mut acc = OctaveBillowAccumulator(0.0);
+        acc.accumulate(octave_result, octave);
+        acc
+    }
+}
+
+impl<const N: usize, T: NoiseConverter<SNorm, Input = T>> PreAccumulator<T, WeightedOctave, N>
+    for TurbulenceOctaveSum
+{
+    type Accumulator = OctaveBillowAccumulator;
+
+    #[inline]
+    fn start_accumulate(self, octave_result: T, octave: &WeightedOctave) -> Self::Accumulator {
+        PreAccumulator::<T, WeightedOctave, N>::start_accumulate(
+            OctaveBillow,
+            octave_result,
+            octave,
+        )
+    }
+}
+
+/// A [`PreAccumulator`] for ridged multifractal noise. Each octave is converted to [`SNorm`] and
+/// folded into a ridge by `1 - |value|` before being weighted and summed. The result is in
+/// [`UNorm`] range, with ridges near 1.0. See also [`OctaveRidgedFeedback`].
+pub struct OctaveRidged;
+
+/// Like [`OctaveRidged`], but each octave is also scaled by the previous octave's ridge times
+/// `gain`, clamped to 1.0. This keeps the detail of higher octaves on the ridges and smooths out
+/// the valleys between them, which is the classic ridged multifractal look.
+pub struct OctaveRidgedFeedback {
+    /// How much each octave's ridge affects the next octave. Around 2.0 is typical.
+    pub gain: f32,
+}
+
+/// The [`Accumulator`] for [`OctaveRidged`] and [`OctaveRidgedFeedback`].
+pub struct OctaveRidgedAccumulator {
+    /// The running sum of the octaves.
+    pub sum: f32,
+    /// The scale of the next octave, from the previous octave's ridge.
+    pub feedback: f32,
+    /// The gain of the feedback. 0.0 disables feedback.
+    pub gain: f32,
+}
+
+impl OctaveRidgedAccumulator {
+    /// Creates a new, empty [`OctaveRidgedAccumulator`] with this feedback `gain`.
+    pub fn new(gain: f32) -> Self {
+        Self {
+            sum: 0.0,
+            feedback: 1.0,
+            gain,
+        }
+    }
+}
+
+impl PostAccumulator for OctaveRidgedAccumulator {
+    type Final = UNorm;
+
+    #[inline]
+    fn finish(self) -> Self::Final {
+        UNorm::new_clamped(self.sum)
+    }
+}
+
+impl<T: NoiseConverter<SNorm, Input = T>> Accumulator<T, WeightedOctave>
+    for OctaveRidgedAccumulator
+{
+    #[inline]
+    fn accumulate(&mut self, octave_result: T, octave: &WeightedOctave) {
+        let magnitude = T::convert(octave_result).split_to_unorm().adapt::<f32>();
+        let ridge = (1.0 - magnitude) * self.feedback;
+        self.sum += ridge * octave.0.adapt::<f32>();
+        if self.gain > 0.0 {
+            self.feedback = (ridge * self.gain).clamp(0.0, 1.0);
+        }
+    }
+}
+
+impl<const N: usize, T: NoiseConverter<SNorm, Input = T>> PreAccumulator<T, WeightedOctave, N>
+    for OctaveRidged
+{
+    type Accumulator = OctaveRidgedAccumulator;
+
+    #[inline]
+    fn start_accumulate(self, octave_result: T, octave: &WeightedOctave) -> Self::Accumulator {
+        let mut acc = OctaveRidgedAccumulator::new(0.0);
+        acc.accumulate(octave_result, octave);
+        acc
+    }
+}
+
+impl<const N: usize, T: NoiseConverter<SNorm, Input = T>> PreAccumulator<T, WeightedOctave, N>
+    for OctaveRidgedFeedback
+{
+    type Accumulator = OctaveRidgedAccumulator;
+
+    #[inline]
+    fn start_accumulate(self, octave_result: T, octave: &WeightedOctave) -> Self::Accumulator {
+        let mut acc = OctaveRidgedAccumulator::new(self.gain);
+        acc.accumulate(octave_result, octave);
+        acc
+    }
+}
+
+/// A [`NoiseOp`] that runs `LOW` octaves of `A` followed by `HIGH` octaves of `B`, summing them
+/// together like [`OctaveSum`]. This lets the low frequencies use different noise than the high
+/// frequencies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MixedFbm2<A, B, const LOW: usize, const HIGH: usize> {
+    low: [(A, WeightedOctave); LOW],
+    high: [(B, WeightedOctave); HIGH],
+}
+
+impl<A, B, const LOW: usize, const HIGH: usize> MixedFbm2<A, B, LOW, HIGH> {
+    /// Constructs a new [`MixedFbm2`] from its `settings`, building each octave's noise from its
+    /// period.
+    pub fn new(
+        mut settings: StandardFbm,
+        mut low: impl FnMut(Period) -> A,
+        mut high: impl FnMut(Period) -> B,
+    ) -> Self {
+        let mut gen_octave = || {
+            let octave = settings.gen_octave::<StandardOctave>();
+            octave.post_construction(&mut settings);
+            octave
+        };
+        let low_octaves: [StandardOctave; LOW] = core::array::from_fn(|_| gen_octave());
+        let high_octaves: [StandardOctave; HIGH] = core::array::from_fn(|_| gen_octave());
+        Self {
+            low: low_octaves.map(|octave| {
+                let (stored, period) = octave.finalize(&settings);
+                (low(period), stored)
+            }),
+            high: high_octaves.map(|octave| {
+                let (stored, period) = octave.finalize(&settings);
+                (high(period), stored)
+            }),
+        }
+    }
+}
+
+impl<I: Copy, A: NoiseOp<I>, B: NoiseOp<I>, const LOW: usize, const HIGH: usize> NoiseOp<I>
+    for MixedFbm2<A, B, LOW, HIGH>
+where
+    A::Output: NoiseConverter<f32, Input = A::Output>,
+    B::Output: NoiseConverter<f32, Input = B::Output>,
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        let mut acc = OctaveSumAccumulator(0.0);
+        for (noise, octave) in &self.low {
+            acc.accumulate(noise.get(input), octave);
+        }
+        for (noise, octave) in &self.high {
+            acc.accumulate(noise.get(input), octave);
+        }
+        acc.finish()
+    }
+}
+
+/// A [`NoiseOp`] that isolates the features of `N` between two periods, like a band-pass filter.
+/// `N` is blurred over a box [`high_period`](Self::high_period) wide and over a box
+/// [`low_period`](Self::low_period) wide, and the result is their difference. This is the noise
+/// analog of a difference of gaussians: features much larger than `low_period` survive both blurs
+/// and cancel out, and features much smaller than `high_period` are blurred away by both, so only
+/// the features in between remain.
+///
+/// Each blur averages `TAPS` samples along each axis. The wide blur averages the narrow blur
+/// instead of `N` directly, so features too small for its taps are already blurred away instead of
+/// aliasing into the band. This samples `N` `TAPS^d + TAPS^(2d)` times, where `d` is the number of
+/// dimensions of the input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandPass<N, const TAPS: usize = 4> {
+    /// The larger period, where the low frequency features are.
+    pub low_period: Period,
+    /// The smaller period, where the high frequency features are.
+    pub high_period: Period,
+    /// The noise to filter.
+    pub noise: N,
+}
+
+/// Averages `sample` over `taps^D` evenly spaced points in a box `width` wide, centered on
+/// `center`.
+#[inline]
+fn box_blur<T: Add<Output = T> + Copy, const D: usize>(
+    center: T,
+    width: f32,
+    taps: usize,
+    from_array: impl Fn([f32; D]) -> T,
+    mut sample: impl FnMut(T) -> f32,
+) -> f32 {
+    let count = taps.pow(D as u32);
+    let mut total = 0.0;
+    for i in 0..count {
+        let mut index = i;
+        let offset = core::array::from_fn(|_| {
+            let tap = index % taps;
+            index /= taps;
+            ((tap as f32 + 0.5) / taps as f32 - 0.5) * width
+        });
+        total += sample(center + from_array(offset));
+    }
+    total / count as f32
+}
+
+/// easily implements [`BandPass`] for different inputs
+macro_rules! impl_band_pass {
+    ($t:ty, $from_array:expr) => {
+        impl<N: NoiseOp<$t>, const TAPS: usize> NoiseOp<$t> for BandPass<N, TAPS>
+        where
+            N::Output: NoiseConverter<f32, Input = N::Output>,
+        {
+            type Output = SNorm;
+
+            #[inline]
+            fn get(&self, input: $t) -> Self::Output {
+                let taps = TAPS.max(1);
+                let high = |point: $t| {
+                    box_blur(point, self.high_period.0, taps, $from_array, |point| {
+                        self.noise.get(point).adapt::<f32>()
+                    })
+                };
+                let low = box_blur(input, self.low_period.0, taps, $from_array, &high);
+                SNorm::new_clamped(high(input) - low)
+            }
+        }
+    };
+}
+
+impl_band_pass!(f32, |[x]: [f32; 1]| x);
+impl_band_pass!(Vec2, Vec2::from_array);
+impl_band_pass!(Vec3, Vec3::from_array);
+impl_band_pass!(Vec4, Vec4::from_array);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as noiz,
+        noise::{
+            SpatialNoiseSettings,
+            noise_op,
+            presets::PerlinNoise,
+        },
+    };
+
+    noise_op! {
+        struct RidgedNoise for Vec2 -> UNorm = SpatialNoiseSettings
+        impl
+        loop OctaveRidged where fbm = StandardFbm::new(args.period, 0.5, 0.5) enum [
+            4 where octave: WeightedOctave as fbm.gen_octave::<StandardOctave>() impl {
+                || *input;
+                fn PerlinNoise = args.branch().with_period(octave).into();
+            },
+        ];
+    }
+
+    noise_op! {
+        struct RidgedFeedbackNoise for Vec2 -> UNorm = SpatialNoiseSettings
+        impl
+        loop OctaveRidgedFeedback { gain: 2.0 } where fbm = StandardFbm::new(args.period, 0.5, 0.5) enum [
+            4 where octave: WeightedOctave as fbm.gen_octave::<StandardOctave>() impl {
+                || *input;
+                fn PerlinNoise = args.branch().with_period(octave).into();
+            },
+        ];
+    }
+
+    noise_op! {
+        struct BillowNoise for Vec2 -> UNorm = SpatialNoiseSettings
+        impl
+        loop OctaveBillow where fbm = StandardFbm::new(args.period, 0.5, 0.5) enum [
+            4 where octave: WeightedOctave as fbm.gen_octave::<StandardOctave>() impl {
+                || *input;
+                fn PerlinNoise = args.branch().with_period(octave).into();
+            },
+        ];
+    }
+
+    #[test]
+    fn billow_is_magnitude() {
+        let full = WeightedOctave(UNorm::new_clamped(1.0));
+        for value in [-0.75, -0.2, 0.0, 0.4] {
+            let acc = PreAccumulator::<_, _, 0>::start_accumulate(
+                OctaveBillow,
+                SNorm::new_clamped(value),
+                &full,
+            );
+            assert!((acc.finish().adapt::<f32>() - value.abs()).abs() < 1e-6);
+        }
+        let middle = PreAccumulator::<_, _, 0>::start_accumulate(
+            OctaveBillow,
+            UNorm::new_clamped(0.5),
+            &full,
+        );
+        assert!(middle.finish().adapt::<f32>() < 1e-6);
+
+        let billow = BillowNoise::from(SpatialNoiseSettings::new(4, 16.0));
+        for y in -20..20 {
+            for x in -20..20 {
+                let value = billow
+                    .get(Vec2::new(x as f32, y as f32) * 1.7)
+                    .adapt::<f32>();
+                assert!((0.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn turbulence_sums_magnitudes() {
+        let octaves = [0.5, 0.3, 0.2].map(|weight| WeightedOctave(UNorm::new_clamped(weight)));
+        let values = [-0.8, 0.4, -0.6];
+        let mut turbulence = PreAccumulator::<_, _, 3>::start_accumulate(
+            TurbulenceOctaveSum,
+            SNorm::new_clamped(values[0]),
+            &octaves[0],
+        );
+        let mut sum =
+            PreAccumulator::<_, _, 3>::start_accumulate(OctaveSum, values[0], &octaves[0]);
+        for (value, octave) in values.into_iter().zip(&octaves).skip(1) {
+            turbulence.accumulate(SNorm::new_clamped(value), octave);
+            sum.accumulate(value, octave);
+        }
+        let turbulence = turbulence.finish().adapt::<f32>();
+        assert!((0.0..=1.0).contains(&turbulence));
+        assert!((turbulence - 0.64).abs() < 1e-5);
+        assert!((turbulence - sum.finish()).abs() > 0.5);
+    }
+
+    #[test]
+    fn ridged_fbm_is_unorm() {
+        let ridged = RidgedNoise::from(SpatialNoiseSettings::new(17, 32.0));
+        let feedback = RidgedFeedbackNoise::from(SpatialNoiseSettings::new(17, 32.0));
+        let mut highest = 0f32;
+        for y in -20..20 {
+            for x in -20..20 {
+                let input = Vec2::new(x as f32, y as f32) * 3.1;
+                let value = ridged.get(input).adapt::<f32>();
+                assert!((0.0..=1.0).contains(&value));
+                highest = highest.max(value);
+                let value = feedback.get(input).adapt::<f32>();
+                assert!((0.0..=1.0).contains(&value));
+            }
+        }
+        assert!(highest > 0.8);
+    }
+
+    struct Constant(Period, f32);
+
+    impl NoiseOp<f32> for Constant {
+        type Output = f32;
+
+        fn get(&self, _input: f32) -> Self::Output {
+            self.1
+        }
+    }
+
+    #[test]
+    fn mixed_fbm_splits_octaves() {
+        let fbm = MixedFbm2::<Constant, Constant, 1, 3>::new(
+            StandardFbm::new(Period(8.0), 0.5, 1.0),
+            |period| Constant(period, 1.0),
+            |period| Constant(period, 0.0),
+        );
+        assert_eq!(fbm.low[0].0.0, Period(8.0));
+        assert_eq!(
+            fbm.high.each_ref().map(|h| h.0.0),
+            [4.0, 2.0, 1.0].map(Period)
+        );
+        assert!((fbm.get(0.0) - 0.25).abs() < 1e-6);
+    }
+
+    /// A single frequency wave, with this many cycles per unit.
+    struct Wave(f32);
+
+    impl NoiseOp<f32> for Wave {
+        type Output = f32;
+
+        fn get(&self, input: f32) -> Self::Output {
+            (input * self.0 * core::f32::consts::TAU).cos()
+        }
+    }
+
+    impl NoiseOp<Vec2> for Wave {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            self.get(input.x - input.y)
+        }
+    }
+
+    #[test]
+    fn band_pass_cancels_outside_band() {
+        let band = |frequency| BandPass::<_, 4> {
+            low_period: Period(4.0),
+            high_period: Period(1.0),
+            noise: Wave(frequency),
+        };
+        // features much larger than the low period.
+        let below = band(1.0 / 128.0);
+        // features between the two periods.
+        let within = band(1.0 / 2.5);
+        // features at and below the high period, which the taps could alias.
+        let above = [1.0, 2.0, 4.0].map(band);
+        let mut within_energy = 0.0;
+        for i in 0..100 {
+            let x = i as f32 * 0.37;
+            assert!(below.get(x).adapt::<f32>().abs() < 0.01);
+            within_energy += within.get(x).adapt::<f32>().abs();
+            for above in &above {
+                assert!(above.get(x).adapt::<f32>().abs() < 0.01);
+            }
+        }
+        assert!(within_energy > 30.0);
+
+        // the same waves, running diagonally in 2d.
+        let input = Vec2::new(3.0, -1.5);
+        assert!(below.get(input).adapt::<f32>().abs() < 0.01);
+        assert!(within.get(input).adapt::<f32>().abs() > 0.1);
+        for above in &above {
+            assert!(above.get(input).adapt::<f32>().abs() < 0.01);
+        }
+    }
+
+    #[cfg(feature = "reflect")]
+    #[test]
+    fn standard_fbm_reflects_fields() {
+        use bevy_reflect::{
+            GetField,
+            Struct,
+        };
+
+        let fbm = StandardFbm::new(Period(32.0), 0.5, 0.6);
+        let names: Vec<_> = (0..fbm.field_len())
+            .filter_map(|index| fbm.name_at(index))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "next_period",
+                "next_weight",
+                "octave_scaling",
+                "octave_fall_off",
+                "total_weight"
+            ]
+        );
+        assert_eq!(fbm.get_field::<f32>("octave_fall_off"), Some(&0.6));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn standard_fbm_round_trips() {
+        let mut fbm = StandardFbm::new(Period(32.0), 0.5, 0.6);
+        let _ = fbm.gen_octave::<StandardOctave>();
+        let json = serde_json::to_string(&fbm).unwrap();
+        let back: StandardFbm = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.next_period, fbm.next_period);
+        assert_eq!(back.next_weight, fbm.next_weight);
+        assert_eq!(back.octave_scaling, fbm.octave_scaling);
+        assert_eq!(back.octave_fall_off, fbm.octave_fall_off);
+        assert_eq!(back.tallied_weight(), fbm.tallied_weight());
+    }
+}
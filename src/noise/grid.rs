@@ -25,7 +25,15 @@ use super::{
     associating::Associated,
     conversions::convertible,
     norm::make_nonzero_f32,
-    seeded::SeedableNoiseType,
+    seeded::{
+        SeedableNoiseType,
+        Seeded,
+        Seeding,
+    },
+    simplex::{
+        SKEW_2D,
+        UNSKEW_2D,
+    },
     smoothing::LerpLocatable,
 };
 use crate::spatial::{
@@ -54,6 +62,8 @@ use crate::spatial::{
 
 /// a noise that converts a vector input to a point in a grid
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct GridNoise {
     /// the frequency of the gridlines
     pub frequency: f32,
@@ -77,8 +87,44 @@ impl From<Period> for GridNoise {
     }
 }
 
+/// a noise that converts a vector input to a point in a grid that repeats every `period_cells`
+/// cells. The corners and surroundings of the [`WrappingGridPoint`]s it produces have their bases
+/// wrapped, so cells on opposite edges of the tile share the same seeds. This makes any noise
+/// seeded from them, like value, perlin, or worley noise, tile seamlessly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappingGridNoise<V = UVec2> {
+    /// the frequency of the gridlines
+    pub frequency: f32,
+    /// the number of cells along each axis before the grid repeats
+    pub period_cells: V,
+}
+
+impl<V> WrappingGridNoise<V> {
+    /// constructs a new [`WrappingGridNoise`] with cells of this `period` that repeats every
+    /// `tiles` cells. Each tile is `period * tiles` wide.
+    pub fn new_tiling(period: f32, tiles: V) -> Self {
+        Self {
+            frequency: 1.0 / make_nonzero_f32(period),
+            period_cells: tiles,
+        }
+    }
+}
+
+/// A grid point from a [`WrappingGridNoise`]. This is a normal grid point, but its corners and
+/// surroundings are wrapped to the tile.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WrappingGridPoint<P, V> {
+    /// the grid point itself, with its base already wrapped
+    pub point: P,
+    /// the number of cells along each axis before the grid repeats
+    pub period_cells: V,
+}
+
+impl<P, V> NoiseType for WrappingGridPoint<P, V> {}
+
 /// a noise that converts a vector input to a point in a grid
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GridNoise64 {
     /// the frequency of the gridlines
     pub frequency: f64,
@@ -133,6 +179,64 @@ impl From<Period> for GridNoiseInt {
     }
 }
 
+/// a noise that converts a 2d input to the triangle of the simplex lattice it is in.
+/// This produces the three seeded corners of the triangle, each with its
+/// [`offset`](GridPoint2::offset) from that corner to the input, associated with the barycentric
+/// weights of the input in the triangle. See [`SmoothBary`](super::smoothing::SmoothBary) to blend
+/// values at the corners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriangleGrid {
+    /// the frequency of the lattice
+    pub frequency: f32,
+    /// the seed given to each corner
+    pub seed: u32,
+}
+
+impl TriangleGrid {
+    /// constructs a new [`TriangleGrid`] of this frequency and seed
+    pub fn new_frequency(frequency: f32, seed: u32) -> Self {
+        Self { frequency, seed }
+    }
+
+    /// constructs a new [`TriangleGrid`] of this period and seed
+    pub fn new_period(period: f32, seed: u32) -> Self {
+        Self::new_frequency(1.0 / make_nonzero_f32(period), seed)
+    }
+}
+
+impl NoiseOp<Vec2> for TriangleGrid {
+    type Output = Associated<[Seeded<GridPoint2>; 3], [f32; 3]>;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let input = input * self.frequency;
+        let skewed = input + Vec2::splat(input.element_sum() * SKEW_2D);
+        let cell = skewed.floor();
+        let local = skewed - cell;
+        let origin = cell - Vec2::splat(cell.element_sum() * UNSKEW_2D);
+        let offset = input - origin;
+        // skewing is linear, so the weights can be taken in skewed space, where the triangle is
+        // half of a unit square.
+        let (middle, weights) = if local.x > local.y {
+            (IVec2::X, [1.0 - local.x, local.x - local.y, local.y])
+        } else {
+            (IVec2::Y, [1.0 - local.y, local.y - local.x, local.x])
+        };
+        let cell = cell.as_ivec2();
+        let seeding = Seeding(self.seed);
+        Associated {
+            value: [IVec2::ZERO, middle, IVec2::ONE].map(|corner| {
+                seeding.get(GridPoint2 {
+                    base: (cell + corner).adapt::<UVec2>(),
+                    offset: offset - corner.as_vec2()
+                        + Vec2::splat(corner.element_sum() as f32 * UNSKEW_2D),
+                })
+            }),
+            meta: weights,
+        }
+    }
+}
+
 /// easily creates grid points
 macro_rules! make_grid_point {
     (
@@ -296,6 +400,72 @@ make_grid_point!(
     4
 );
 
+/// represents a point in a 1d grid
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GridPoint1 {
+    /// the edge of the grid cell we are anchored to
+    pub base: u32,
+    /// the offset from the [`base`](Self::base)
+    pub offset: f32,
+}
+
+impl GridPoint1 {
+    /// pushes the grid point by this offset
+    #[inline]
+    pub fn pushed(&self, push: u32) -> Self {
+        Self {
+            base: self.base + push,
+            offset: self.offset - push as f32,
+        }
+    }
+
+    /// Produces the two ends of the grid cell, from the current value.
+    #[inline]
+    pub fn corners(&self) -> [Self; 2] {
+        [self.clone(), self.pushed(1)]
+    }
+}
+
+impl NoiseType for GridPoint1 {}
+
+impl SeedableNoiseType for GridPoint1 {
+    #[inline]
+    fn generate_seed(&self, seed: u32) -> u32 {
+        self.base.generate_seed(seed)
+    }
+}
+
+convertible!(GridPoint1 = u32, |source| source.base);
+convertible!(GridPoint1 = f32, |source| source.offset);
+
+impl NoiseOp<f32> for GridNoise {
+    type Output = GridPoint1;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        let val = input * self.frequency;
+        let floor = val.floor();
+        GridPoint1 {
+            base: (floor as i32).adapt::<u32>(),
+            offset: val - floor,
+        }
+    }
+}
+
+impl LerpLocatable for GridPoint1 {
+    type Location = [f32; 1];
+
+    type Extents = [Self; 2];
+
+    #[inline]
+    fn prepare_lerp(self) -> Associated<Self::Extents, Self::Location> {
+        Associated {
+            meta: [self.offset],
+            value: self.corners(),
+        }
+    }
+}
+
 convertible!(GridPointD2 = GridPoint2, |source| GridPoint2 {
     offset: source.offset.as_vec2(),
     base: source.base.as_uvec2(),
@@ -381,3 +551,149 @@ impl GridPoint4 {
         UNIT_SURROUNDINGS_IVEC4.map(|d| minus_corner.pushed((d + IVec4::ONE).as_uvec4()))
     }
 }
+
+/// easily implements [`WrappingGridNoise`] for different dimensions
+macro_rules! impl_wrapping_grid {
+    ($point:ident, $uint:ty, $f:ty, $d_2:ident, $d_3:ident, $axies:ident) => {
+        impl NoiseOp<$f> for WrappingGridNoise<$uint> {
+            type Output = WrappingGridPoint<$point, $uint>;
+
+            #[inline]
+            fn get(&self, input: $f) -> Self::Output {
+                let period_cells = self.period_cells.max(<$uint>::ONE);
+                let mut point = GridNoise::new_frequency(self.frequency).get(input);
+                point.base %= period_cells;
+                WrappingGridPoint {
+                    point,
+                    period_cells,
+                }
+            }
+        }
+
+        impl WrappingGridPoint<$point, $uint> {
+            /// wraps the base of this grid point to the tile.
+            #[inline]
+            fn wrap(&self, mut point: $point) -> $point {
+                point.base %= self.period_cells;
+                point
+            }
+
+            /// Produces an array of all positive unit offset combinations from the current value,
+            /// wrapped to the tile.
+            #[inline]
+            pub fn corners(&self) -> $d_2<$point> {
+                self.point.corners().map(|point| self.wrap(point))
+            }
+
+            /// Produces an array of all unit offset combinations from the current value, wrapped to
+            /// the tile.
+            #[inline]
+            pub fn surroundings(&self) -> $d_3<$point> {
+                // moving the base out of the first cell of the tile keeps the negative neighbors
+                // from underflowing.
+                let mut shifted = self.point.clone();
+                shifted.base += self.period_cells;
+                shifted.surroundings().map(|point| self.wrap(point))
+            }
+        }
+
+        impl SeedableNoiseType for WrappingGridPoint<$point, $uint> {
+            #[inline]
+            fn generate_seed(&self, seed: u32) -> u32 {
+                self.point.generate_seed(seed)
+            }
+        }
+
+        impl LerpLocatable for WrappingGridPoint<$point, $uint> {
+            type Location = $axies<f32>;
+
+            type Extents = $d_2<$point>;
+
+            #[inline]
+            fn prepare_lerp(self) -> Associated<Self::Extents, Self::Location> {
+                Associated {
+                    value: self.corners(),
+                    meta: self.point.offset.to_array().into(),
+                }
+            }
+        }
+    };
+}
+
+impl_wrapping_grid!(GridPoint2, UVec2, Vec2, Corners2d, Surroundings2d, Axies2d);
+impl_wrapping_grid!(GridPoint3, UVec3, Vec3, Corners3d, Surroundings3d, Axies3d);
+impl_wrapping_grid!(GridPoint4, UVec4, Vec4, Corners4d, Surroundings4d, Axies4d);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise::{
+            norm::UNorm,
+            seeded::Seeding,
+            smoothing::{
+                Lerp,
+                LerpValues,
+                Smooth,
+                SmoothBary,
+            },
+        },
+        spatial::interpolating::Cubic,
+    };
+
+    #[test]
+    fn triangle_grid_blends_barycentrically() {
+        let grid = TriangleGrid::new_frequency(1.0, 7);
+        let value = |corner: Seeded<GridPoint2>| corner.rng(0).adapt::<UNorm>().adapt::<f32>();
+        for i in 0..200 {
+            let input = Vec2::new(i as f32 * 0.173 - 17.0, (i * 7 % 23) as f32 * 0.41 - 3.0);
+            let weights = grid.get(input).meta;
+            assert!((weights.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+            assert!(weights.iter().all(|weight| *weight >= 0.0));
+        }
+        for y in -3..3 {
+            for x in -3..3 {
+                let lattice = IVec2::new(x, y).as_vec2();
+                let input = lattice - Vec2::splat(lattice.element_sum() * UNSKEW_2D);
+                let triangle = grid.get(input);
+                let (corner, _) = triangle
+                    .value
+                    .iter()
+                    .zip(triangle.meta)
+                    .find(|(_, weight)| *weight > 0.999)
+                    .unwrap();
+                assert_eq!(corner.value.base, IVec2::new(x, y).adapt::<UVec2>());
+                let expected = value(corner.clone());
+                let blended = SmoothBary.get(triangle.map(|corners| corners.map(value)));
+                assert!((blended - expected).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn wrapping_grid_tiles() {
+        let grid = WrappingGridNoise::new_tiling(4.0, UVec2::new(5, 3));
+        let seeding = Seeding(3);
+        let value = |input: Vec2| {
+            let ready = Lerp.get(grid.get(input)).map(|LerpValues(corners)| {
+                LerpValues(
+                    corners
+                        .map(|corner| seeding.get(corner).rng(0).adapt::<UNorm>().adapt::<f32>()),
+                )
+            });
+            Smooth(Cubic).get(ready)
+        };
+        let size = Vec2::new(20.0, 12.0);
+        let mut buffer = [[0.0; 17]; 17];
+        for (y, row) in buffer.iter_mut().enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = value(Vec2::new(x as f32, y as f32) / 16.0 * size);
+            }
+        }
+        for row in &buffer {
+            assert_eq!(row[0], row[16]);
+        }
+        assert_eq!(buffer[0], buffer[16]);
+        assert_ne!(buffer[3][5], buffer[8][5]);
+    }
+}
@@ -0,0 +1,574 @@
+//! This module contains noise operations that treat scalar noise as a heightfield or density
+//! field, often sampling it around the input to analyze its shape.
+
+use bevy_math::{
+    Vec2,
+    Vec3,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    conversions::NoiseConverter,
+    norm::{
+        SNorm,
+        UNorm,
+    },
+};
+
+/// Samples `noise` as an `f32`.
+#[inline]
+fn height<N: NoiseOp<Vec2>>(noise: &N, at: Vec2) -> f32
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    noise.get(at).adapt::<f32>()
+}
+
+/// Estimates the gradient of `noise` at `at` with central differences `step` away.
+#[inline]
+fn gradient<N: NoiseOp<Vec2>>(noise: &N, at: Vec2, step: f32) -> Vec2
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    let dx = Vec2::new(step, 0.0);
+    let dy = Vec2::new(0.0, step);
+    Vec2::new(
+        height(noise, at + dx) - height(noise, at - dx),
+        height(noise, at + dy) - height(noise, at - dy),
+    ) / (2.0 * step)
+}
+
+/// Averages `noise` at 8 points evenly spaced on a circle of `radius` around `at`.
+#[inline]
+fn neighborhood_mean<N: NoiseOp<Vec2>>(noise: &N, at: Vec2, radius: f32) -> f32
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    const NEIGHBORS: usize = 8;
+    (0..NEIGHBORS)
+        .map(|i| {
+            let angle = i as f32 * core::f32::consts::TAU / NEIGHBORS as f32;
+            height(noise, at + Vec2::from_angle(angle) * radius)
+        })
+        .sum::<f32>()
+        / NEIGHBORS as f32
+}
+
+/// A [`NoiseOp`] that finds the dominant direction of variation of `N` around the input.
+/// The result is a unit vector along which `N` changes the most, or [`Vec2::ZERO`] if `N` is flat.
+/// Since this is an axis, not a direction, it may point either way along it.
+///
+/// This samples the gradient of `N` at the input and `radius` away from it on each axis, summing
+/// their outer products into a structure tensor.
+/// The tensor is a 2x2 symmetric matrix `[a, b; b, c]`, whose principal eigenvector is at an angle
+/// of `atan2(2b, a - c) / 2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructureDirection<N> {
+    /// How far apart the gradients are sampled.
+    pub radius: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for StructureDirection<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = Vec2;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let step = self.radius * 0.5;
+        let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+        for offset in [
+            Vec2::ZERO,
+            Vec2::new(self.radius, 0.0),
+            Vec2::new(-self.radius, 0.0),
+            Vec2::new(0.0, self.radius),
+            Vec2::new(0.0, -self.radius),
+        ] {
+            let g = gradient(&self.noise, input + offset, step);
+            a += g.x * g.x;
+            b += g.x * g.y;
+            c += g.y * g.y;
+        }
+
+        if a + c <= f32::EPSILON {
+            return Vec2::ZERO;
+        }
+        Vec2::from_angle(0.5 * (2.0 * b).atan2(a - c))
+    }
+}
+
+/// A [`NoiseOp`] that measures how much lower the input is than its surroundings `radius` away,
+/// which is useful for placing water or moss in crevices.
+/// The result is 0.5 where the heightfield is flat or sloped evenly, higher in pits and lower on
+/// peaks, reaching 1.0 when the input is 0.5 lower than the average of its surroundings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cavity<N> {
+    /// How far away the surroundings are sampled.
+    pub radius: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for Cavity<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let depth = neighborhood_mean(&self.noise, input, self.radius) - height(&self.noise, input);
+        UNorm::new_clamped(0.5 + depth)
+    }
+}
+
+/// A [`NoiseOp`] that estimates how open the sky is above the input, which is useful for placing
+/// snow or brightening exposed areas. This is the opposite of [`Cavity`]: `N` is sampled at
+/// `samples` points evenly spaced on a circle of `radius` around the input, and the result is the
+/// fraction of them that are lower than the input. Peaks are 1.0 and pits are 0.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Openness<N> {
+    /// How far away the surroundings are sampled.
+    pub radius: f32,
+    /// How many surroundings are sampled. This is at least 1.
+    pub samples: u32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for Openness<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let center = height(&self.noise, input);
+        let samples = self.samples.max(1);
+        let lower = (0..samples)
+            .filter(|i| {
+                let angle = *i as f32 * core::f32::consts::TAU / samples as f32;
+                height(&self.noise, input + Vec2::from_angle(angle) * self.radius) < center
+            })
+            .count();
+        UNorm::new_clamped(lower as f32 / samples as f32)
+    }
+}
+
+/// A [`NoiseOp`] that measures the curvature of `N` at the input, which is useful for masking
+/// ridges and valleys. The result is positive in valleys and pits, negative on ridges and peaks,
+/// and 0.0 where `N` is flat or sloped evenly.
+///
+/// This uses the 5-sample stencil for the Laplacian: `N` is sampled at the input and `epsilon` away
+/// from it in each direction along each axis. The result is the sum of the four neighbors minus
+/// four times the center, divided by four. For a `N` in [`UNorm`] range, that is always within
+/// [`SNorm`] range, and it is the Laplacian scaled by `epsilon^2 / 4`, so a larger `epsilon`
+/// detects broader, gentler curves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curvature<N> {
+    /// How far away the neighbors are sampled.
+    pub epsilon: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for Curvature<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = SNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let dx = Vec2::new(self.epsilon, 0.0);
+        let dy = Vec2::new(0.0, self.epsilon);
+        let neighbors = height(&self.noise, input + dx)
+            + height(&self.noise, input - dx)
+            + height(&self.noise, input + dy)
+            + height(&self.noise, input - dy);
+        SNorm::new_clamped((neighbors - 4.0 * height(&self.noise, input)) * 0.25)
+    }
+}
+
+/// A [`NoiseOp`] that estimates how much detail `N` has around the input, which is useful for
+/// picking a level of detail. The result is how far the input is from the average of its
+/// surroundings `radius` away, so it is near 0.0 where `N` is flat or sloped evenly, and higher
+/// where `N` varies a lot over the `radius`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyContent<N> {
+    /// How far away the surroundings are sampled.
+    pub radius: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for FrequencyContent<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let difference =
+            neighborhood_mean(&self.noise, input, self.radius) - height(&self.noise, input);
+        UNorm::new_clamped(difference.abs())
+    }
+}
+
+/// A [`NoiseOp`] that finds the direction `N` rises in at the input, which is useful for
+/// visualizing flow by mapping the result to a hue. The gradient is estimated with central
+/// differences `epsilon` away, and its angle is mapped from -π..=π to 0..=1, so a gradient along
+/// +X is 0.5. Where `N` is flat, the result is also 0.5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientAngle<N> {
+    /// How far away the gradient is sampled.
+    pub epsilon: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for GradientAngle<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let gradient = gradient(&self.noise, input, self.epsilon);
+        let angle = gradient.y.atan2(gradient.x);
+        UNorm::new_clamped(angle / core::f32::consts::TAU + 0.5)
+    }
+}
+
+/// A [`NoiseOp`] that masks where `N` is above `threshold`, antialiased over one unit of input,
+/// typically a pixel. Unlike a hard threshold, the result is the approximate fraction of that pixel
+/// that is above the threshold, found by dividing the distance from the threshold by the slope of
+/// `N`. This is 0.5 exactly on the threshold, and 1.0 or 0.0 half a pixel away from it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothMask<N> {
+    /// The value of `N` where the mask's edge is.
+    pub threshold: f32,
+    /// The noise being masked.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for SmoothMask<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let above = height(&self.noise, input) - self.threshold;
+        let slope = gradient(&self.noise, input, 0.5).length();
+        if slope <= f32::EPSILON {
+            // a flat field has no edge to smooth.
+            let coverage = if above == 0.0 {
+                0.5
+            } else {
+                0.5 + 0.5 * above.signum()
+            };
+            return UNorm::new_clamped(coverage);
+        }
+        UNorm::new_clamped(0.5 + above / slope)
+    }
+}
+
+/// A [`NoiseOp`] that approximates one pass of thermal erosion, where material slides down slopes
+/// steeper than the talus angle. `N` is sampled at the input and one unit away from it along each
+/// axis. For each neighbor, an eighth of the height difference beyond the talus slope moves from
+/// the higher sample to the lower one, and the result is the input's new height.
+///
+/// This is only a single-pass approximation: real thermal erosion repeats this over a whole
+/// heightmap until it settles, but each sample here is computed independently from `N` alone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalErosion<N> {
+    /// The steepest stable slope, in radians.
+    pub talus_angle: f32,
+    /// The heightfield noise.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for ThermalErosion<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let center = height(&self.noise, input);
+        let talus = self.talus_angle.tan();
+        let moved = [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y]
+            .into_iter()
+            .map(|offset| {
+                let difference = center - height(&self.noise, input + offset);
+                let excess = (difference.abs() - talus).max(0.0);
+                excess.copysign(difference) * 0.125
+            })
+            .sum::<f32>();
+        center - moved
+    }
+}
+
+/// A [`NoiseOp`] that produces a 3d density field around a flat surface, as is standard for
+/// marching cubes. The result is `surface_height - input.y`, offset by `N` times `scale`, so it is
+/// positive (solid) below the surface and negative (empty) above it. The noise can carve caves and
+/// overhangs into the surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DensityField<N> {
+    /// The y value of the reference surface.
+    pub surface_height: f32,
+    /// How much `N` affects the density.
+    pub scale: f32,
+    /// The noise that displaces the surface.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec3>> NoiseOp<Vec3> for DensityField<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Vec3) -> Self::Output {
+        (self.surface_height - input.y) + self.noise.get(input).adapt::<f32>() * self.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Ramp(Vec2);
+
+    impl NoiseOp<Vec2> for Ramp {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            input.dot(self.0)
+        }
+    }
+
+    #[test]
+    fn structure_aligns_with_ramp() {
+        let slope = Vec2::new(2.0, 1.0);
+        let direction = StructureDirection {
+            radius: 0.5,
+            noise: Ramp(slope),
+        }
+        .get(Vec2::new(3.0, -1.0));
+        assert!(direction.dot(slope.normalize()).abs() > 0.999);
+
+        let flat = StructureDirection {
+            radius: 0.5,
+            noise: Ramp(Vec2::ZERO),
+        };
+        assert_eq!(flat.get(Vec2::ONE), Vec2::ZERO);
+    }
+
+    /// A cone, with its tip at the origin.
+    struct Cone(f32);
+
+    impl NoiseOp<Vec2> for Cone {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            input.length() * self.0
+        }
+    }
+
+    #[test]
+    fn pits_have_cavity() {
+        let pit = Cavity {
+            radius: 0.5,
+            noise: Cone(1.0),
+        };
+        let peak = Cavity {
+            radius: 0.5,
+            noise: Cone(-1.0),
+        };
+        let slope = Cavity {
+            radius: 0.5,
+            noise: Ramp(Vec2::new(1.0, 2.0)),
+        };
+        assert!(pit.get(Vec2::ZERO).adapt::<f32>() > 0.9);
+        assert!(peak.get(Vec2::ZERO).adapt::<f32>() < 0.1);
+        assert!((slope.get(Vec2::ONE).adapt::<f32>() - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn peaks_are_open() {
+        let openness = |noise| {
+            Openness {
+                radius: 0.5,
+                samples: 12,
+                noise,
+            }
+            .get(Vec2::ZERO)
+            .adapt::<f32>()
+        };
+        assert!(openness(Cone(-1.0)) > 0.99);
+        assert!(openness(Cone(1.0)) < 0.01);
+    }
+
+    #[test]
+    fn steep_slopes_erode() {
+        let talus_angle = core::f32::consts::FRAC_PI_6;
+        let steep = ThermalErosion {
+            talus_angle,
+            noise: Cone(-2.0),
+        };
+        let gentle = ThermalErosion {
+            talus_angle,
+            noise: Cone(-0.2),
+        };
+        let pit = ThermalErosion {
+            talus_angle,
+            noise: Cone(2.0),
+        };
+        assert!(steep.get(Vec2::ZERO) < -0.5);
+        assert!(pit.get(Vec2::ZERO) > 0.5);
+        assert_eq!(gentle.get(Vec2::ZERO), 0.0);
+        // the steep peak is now closer to its neighbors than before.
+        let neighbor = Cone(-2.0).get(Vec2::X);
+        assert!((steep.get(Vec2::ZERO) - neighbor).abs() < 2.0);
+    }
+
+    /// A smooth bump with its peak at the origin.
+    struct Bump;
+
+    impl NoiseOp<Vec2> for Bump {
+        type Output = UNorm;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            UNorm::new_clamped((-input.length_squared()).exp())
+        }
+    }
+
+    #[test]
+    fn bumps_curve_down() {
+        let bump = Curvature {
+            epsilon: 0.25,
+            noise: Bump,
+        };
+        assert!(bump.get(Vec2::ZERO).adapt::<f32>() < -0.01);
+        // the bump flattens out and curves back up far from the peak.
+        assert!(bump.get(Vec2::new(1.5, 0.0)).adapt::<f32>() > 0.0);
+
+        let slope = Curvature {
+            epsilon: 0.25,
+            noise: Ramp(Vec2::new(0.3, 0.1)),
+        };
+        assert!(slope.get(Vec2::ONE).adapt::<f32>().abs() < 1e-5);
+    }
+
+    /// Ripples with peaks and troughs `frequency` radians apart.
+    struct Ripples(f32);
+
+    impl NoiseOp<Vec2> for Ripples {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            (input.x * self.0).sin() * (input.y * self.0).sin() * 0.5
+        }
+    }
+
+    #[test]
+    fn busy_regions_have_more_frequency_content() {
+        let average = |content: &dyn Fn(Vec2) -> f32| {
+            let mut total = 0.0;
+            for y in 0..20 {
+                for x in 0..20 {
+                    total += content(Vec2::new(x as f32, y as f32) * 0.37);
+                }
+            }
+            total / 400.0
+        };
+        let flat = FrequencyContent {
+            radius: 0.5,
+            noise: Ramp(Vec2::new(0.2, -0.1)),
+        };
+        let calm = FrequencyContent {
+            radius: 0.5,
+            noise: Ripples(0.5),
+        };
+        let busy = FrequencyContent {
+            radius: 0.5,
+            noise: Ripples(5.0),
+        };
+        let flat = average(&|at| flat.get(at).adapt::<f32>());
+        let calm = average(&|at| calm.get(at).adapt::<f32>());
+        let busy = average(&|at| busy.get(at).adapt::<f32>());
+        assert!(flat < 1e-5);
+        assert!(calm < busy);
+        assert!(busy > 0.1);
+    }
+
+    #[test]
+    fn gradient_angle_follows_ramp() {
+        let angle = |slope: Vec2| {
+            GradientAngle {
+                epsilon: 0.25,
+                noise: Ramp(slope),
+            }
+            .get(Vec2::new(2.0, -3.0))
+            .adapt::<f32>()
+        };
+        assert!((angle(Vec2::new(0.4, 0.0)) - 0.5).abs() < 1e-5);
+        assert!((angle(Vec2::new(0.0, 0.4)) - 0.75).abs() < 1e-5);
+        assert!((angle(Vec2::new(0.0, -0.4)) - 0.25).abs() < 1e-5);
+        assert!((angle(Vec2::new(-0.3, -0.3)) - 0.125).abs() < 1e-5);
+        assert!((angle(Vec2::ZERO) - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn smooth_mask_is_half_on_threshold() {
+        let mask = SmoothMask {
+            threshold: 3.0,
+            noise: Ramp(Vec2::new(0.5, 0.0)),
+        };
+        let coverage = |x: f32| mask.get(Vec2::new(x, 4.0)).adapt::<f32>();
+        assert!((coverage(6.0) - 0.5).abs() < 1e-5);
+        assert!((coverage(6.25) - 0.75).abs() < 1e-5);
+        assert!(coverage(7.0) > 0.99);
+        assert!(coverage(5.0) < 0.01);
+
+        let flat = SmoothMask {
+            threshold: 0.0,
+            noise: Ramp(Vec2::ZERO),
+        };
+        assert!((flat.get(Vec2::ONE).adapt::<f32>() - 0.5).abs() < 1e-5);
+    }
+
+    struct Zero;
+
+    impl NoiseOp<Vec3> for Zero {
+        type Output = f32;
+
+        fn get(&self, _input: Vec3) -> Self::Output {
+            0.0
+        }
+    }
+
+    #[test]
+    fn density_is_solid_below_surface() {
+        let density = DensityField {
+            surface_height: 10.0,
+            scale: 5.0,
+            noise: Zero,
+        };
+        assert!(density.get(Vec3::new(4.0, 9.5, -3.0)) > 0.0);
+        assert!(density.get(Vec3::new(4.0, 10.5, -3.0)) < 0.0);
+        assert_eq!(density.get(Vec3::new(4.0, 10.0, -3.0)), 0.0);
+    }
+}
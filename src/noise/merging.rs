@@ -16,6 +16,10 @@ use super::{
     NoiseType,
     norm::UNorm,
 };
+use crate::spatial::interpolating::{
+    Cubic,
+    Lerpable,
+};
 
 /// Allows the noise type to be merged
 pub trait Merger<I, M> {
@@ -196,6 +200,32 @@ impl<I: NoiseType, M, T: Orderer<I>> Merger<I, M> for MinOrders<T> {
     }
 }
 
+/// A merger that selects the weights of the `K` values with the least weights, in order.
+/// If you try to merge on an array shorter than `K`, this will return [`f32::INFINITY`], where data
+/// is missing.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct MinOrdersN<const K: usize, T>(pub T);
+
+impl<I: NoiseType, M, T: Orderer<I>, const K: usize> Merger<I, M> for MinOrdersN<K, T> {
+    type Output = [T::OrderingOutput; K];
+
+    #[inline]
+    fn merge(&self, vals: impl IntoIterator<Item = I>, _meta: &M) -> Self::Output {
+        let mut ordering_numbers = [f32::INFINITY; K];
+
+        for val in vals {
+            let mut weight = self.0.ordering_of(&val);
+            for slot in ordering_numbers.iter_mut() {
+                if weight < *slot {
+                    core::mem::swap(&mut weight, slot);
+                }
+            }
+        }
+
+        ordering_numbers.map(|v| self.0.relative_ordering(v))
+    }
+}
+
 /// A merger that selects the weight of the value with the least weight.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub struct MinOrder<T>(pub T);
@@ -369,6 +399,70 @@ impl<I: NoiseType, M, T: Orderer<I>> Merger<I, M> for AverageOrders<T> {
     }
 }
 
+/// Finds the index of the median of up to `N` orderings. Ties keep their original order, and for an
+/// even count, the lower of the two middle values is chosen.
+#[inline]
+fn median_index<const N: usize>(orderings: impl IntoIterator<Item = f32>) -> usize {
+    // an insertion sort, since `N` is expected to be small.
+    let mut sorted = [(f32::INFINITY, 0usize); N];
+    let mut len = 0;
+    for (index, ordering) in orderings.into_iter().take(N).enumerate() {
+        let mut entry = (ordering, index);
+        for slot in sorted[..index].iter_mut() {
+            if entry.0 < slot.0 {
+                core::mem::swap(&mut entry, slot);
+            }
+        }
+        sorted[index] = entry;
+        len = index + 1;
+    }
+
+    sorted
+        .get(len.saturating_sub(1) / 2)
+        .map_or(0, |entry| entry.1)
+}
+
+/// A merger that selects the value with the median weight.
+/// Only the first `N` values are considered. Ties keep their original order, and for an even
+/// count, the lower of the two middle values is chosen.
+/// If you try to merge on an empty array, this will return the default value.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Median<const N: usize, T>(pub T);
+
+impl<I: NoiseType + Default, M, T: Orderer<I>, const N: usize> Merger<I, M> for Median<N, T> {
+    type Output = I;
+
+    #[inline]
+    fn merge(&self, vals: impl IntoIterator<Item = I>, _meta: &M) -> Self::Output {
+        let mut values: [Option<I>; N] = core::array::from_fn(|_| None);
+        for (slot, val) in values.iter_mut().zip(vals) {
+            *slot = Some(val);
+        }
+
+        let index = median_index::<N>(values.iter().flatten().map(|val| self.0.ordering_of(val)));
+        values
+            .get_mut(index)
+            .and_then(Option::take)
+            .unwrap_or_default()
+    }
+}
+
+/// A merger that selects the index of the value with the median weight.
+/// Only the first `N` values are considered. Ties keep their original order, and for an even
+/// count, the lower of the two middle values is chosen.
+/// If you try to merge on an empty array, this will return zero.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct MedianIndex<const N: usize, T>(pub T);
+
+impl<I: NoiseType, M, T: Orderer<I>, const N: usize> Merger<I, M> for MedianIndex<N, T> {
+    type Output = usize;
+
+    #[inline]
+    fn merge(&self, vals: impl IntoIterator<Item = I>, _meta: &M) -> Self::Output {
+        median_index::<N>(vals.into_iter().map(|val| self.0.ordering_of(&val)))
+    }
+}
+
 /// A merger that merges values by assigning them weights.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Weighted<T>(pub T);
@@ -394,10 +488,17 @@ impl<I: NoiseType + Default, M, T: WeightFactorer<I>> Merger<I, M> for Weighted<
         }
 
         let total: f32 = weights.iter().map(|w| w.0).sum();
-        let inv_total = 1.0 / total;
-        let mut iter_weights = weights
-            .into_iter()
-            .map(|(w, v)| self.0.weigh_value(v, w * inv_total));
+        // If nothing has any weight, weigh everything evenly instead of dividing by zero.
+        let even = total <= 0.0 || !total.is_finite();
+        let inv_total = if even {
+            1.0 / weights.len() as f32
+        } else {
+            1.0 / total
+        };
+        let mut iter_weights = weights.into_iter().map(|(w, v)| {
+            let w = if even { 1.0 } else { w };
+            self.0.weigh_value(v, w * inv_total)
+        });
         // SAFETY: We just checked that the weights were not empty.
         let mut result = unsafe { iter_weights.next().unwrap_unchecked() };
         for weight in iter_weights {
@@ -488,6 +589,37 @@ impl<I: NoiseType + Default + Mul<Output = I>, M> Merger<I, M> for Product {
     }
 }
 
+/// A [`Merger`] that takes a smooth minimum of values, blending between them where they are within
+/// `k` of eachother. This uses the polynomial smooth minimum, and a `k` of 0 is a hard minimum.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct SmoothMin {
+    /// The distance between values over which they blend together.
+    pub k: f32,
+}
+
+impl<M> Merger<f32, M> for SmoothMin {
+    type Output = f32;
+
+    #[inline]
+    fn merge(&self, vals: impl IntoIterator<Item = f32>, _meta: &M) -> Self::Output {
+        let mut vals = vals.into_iter();
+        let Some(mut result) = vals.next() else {
+            return 0.0;
+        };
+
+        if self.k <= 0.0 {
+            return vals.fold(result, f32::min);
+        }
+
+        for v in vals {
+            let h = (self.k - (result - v).abs()).max(0.0) / self.k;
+            result = result.min(v) - h * h * self.k * 0.25;
+        }
+
+        result
+    }
+}
+
 /// A noise operation that uses [`Merger`] `M` to merge any [`Mergeable`].
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Merged<M>(pub M);
@@ -510,6 +642,46 @@ impl<const N: usize, I: NoiseType, M: Merger<I, ()>> NoiseOp<[I; N]> for Merged<
     }
 }
 
+/// A [`NoiseOp`] that transitions between two noise pipelines by distance from `center`.
+/// Within `inner_radius`, this is exactly `A`, and beyond `outer_radius`, it is exactly `B`.
+/// Between them, the two are blended with a [`Cubic`] curve. This is useful for shaping terrain
+/// around a point of interest, like a spawn point.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RadialBlend<A, B> {
+    /// The point the radii are measured from.
+    pub center: Vec2,
+    /// Within this distance of the center, only `A` is used.
+    pub inner_radius: f32,
+    /// Beyond this distance of the center, only `B` is used.
+    pub outer_radius: f32,
+    /// The noise used near the center.
+    pub inner: A,
+    /// The noise used far from the center.
+    pub outer: B,
+}
+
+impl<A: NoiseOp<Vec2>, B: NoiseOp<Vec2, Output = A::Output>> NoiseOp<Vec2> for RadialBlend<A, B>
+where
+    A::Output: Lerpable<f32>,
+{
+    type Output = A::Output;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let distance = input.distance(self.center);
+        if distance <= self.inner_radius {
+            return self.inner.get(input);
+        }
+        if distance >= self.outer_radius {
+            return self.outer.get(input);
+        }
+        let t = (distance - self.inner_radius) / (self.outer_radius - self.inner_radius);
+        self.inner
+            .get(input)
+            .mix_dirty(self.outer.get(input), t, &Cubic)
+    }
+}
+
 impl Orderer<f32> for () {
     type OrderingOutput = f32;
 
@@ -540,32 +712,52 @@ impl WeightFactorer<f32> for () {
 
 /// A [`Orderer`] and for "as the crow flyies" distance
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct EuclideanDistance {
     /// represents the inverse of the maximum expected evaluation of this distance.
     pub inv_max_expected: f32,
 }
 
-/// A [`Orderer`] and for "manhatan" or diagonal distance
+/// A [`Orderer`] and for "manhattan" or diagonal distance
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct ManhatanDistance {
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct ManhattanDistance {
     /// represents the inverse of the maximum expected evaluation of this distance.
     pub inv_max_expected: f32,
 }
 
-/// A [`Orderer`] that evenly combines [`EuclideanDistance`] and [`ManhatanDistance`]
+/// The old, misspelled name of [`ManhattanDistance`].
+#[deprecated(note = "renamed to `ManhattanDistance`")]
+pub type ManhatanDistance = ManhattanDistance;
+
+/// A [`Orderer`] that evenly combines [`EuclideanDistance`] and [`ManhattanDistance`]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct HybridDistance {
     /// represents the inverse of the maximum expected evaluation of this distance.
     pub inv_max_expected: f32,
 }
 
-/// A [`Orderer`] that evenly uses Chebyshev distance, which is similar to [`ManhatanDistance`].
+/// A [`Orderer`] that evenly uses Chebyshev distance, which is similar to [`ManhattanDistance`].
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct ChebyshevDistance {
     /// represents the inverse of the maximum expected evaluation of this distance.
     pub inv_max_expected: f32,
 }
 
+/// A [`Orderer`] for minkowski distance, which generalizes [`ManhattanDistance`] (`p` = 1) and
+/// [`EuclideanDistance`] (`p` = 2). Values of `p` less than 1 produce concave, star-like cells, and
+/// large values approach [`ChebyshevDistance`]. `p` must be positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct MinkowskiDistance {
+    /// The power of the distance.
+    pub p: f32,
+    /// represents the inverse of the maximum expected evaluation of this distance.
+    pub inv_max_expected: f32,
+}
+
 macro_rules! impl_distances {
     ($t:path) => {
         impl Orderer<$t> for EuclideanDistance {
@@ -582,7 +774,7 @@ macro_rules! impl_distances {
             }
         }
 
-        impl Orderer<$t> for ManhatanDistance {
+        impl Orderer<$t> for ManhattanDistance {
             type OrderingOutput = UNorm;
 
             #[inline]
@@ -611,6 +803,20 @@ macro_rules! impl_distances {
             }
         }
 
+        impl Orderer<$t> for MinkowskiDistance {
+            type OrderingOutput = UNorm;
+
+            #[inline]
+            fn ordering_of(&self, value: &$t) -> f32 {
+                value.abs().powf(self.p).element_sum()
+            }
+
+            #[inline]
+            fn relative_ordering(&self, ordering: f32) -> Self::OrderingOutput {
+                UNorm::new_clamped(ordering.powf(1.0 / self.p) * self.inv_max_expected)
+            }
+        }
+
         impl Orderer<$t> for ChebyshevDistance {
             type OrderingOutput = UNorm;
 
@@ -630,3 +836,94 @@ macro_rules! impl_distances {
 impl_distances!(Vec2);
 impl_distances!(Vec3);
 impl_distances!(Vec4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Weighs a [`Vec2`] by its x, contributing its y.
+    struct WeightByX;
+
+    impl WeightFactorer<Vec2> for WeightByX {
+        type Output = f32;
+
+        fn weight_of(&self, value: &Vec2) -> f32 {
+            value.x
+        }
+
+        fn weigh_value(&self, value: Vec2, relative_weight: f32) -> Self::Output {
+            value.y * relative_weight
+        }
+    }
+
+    #[test]
+    fn weighted_is_weighted_average() {
+        let merged = Merged(Weighted(WeightByX));
+        let vals = [
+            Vec2::new(1.0, 2.0),
+            Vec2::new(2.0, 5.0),
+            Vec2::new(1.0, -1.0),
+        ];
+        assert!((merged.get(vals) - 11.0 / 4.0).abs() < 1e-6);
+        assert_eq!(merged.get([Vec2::ZERO; 0]), 0.0);
+
+        let weightless = [Vec2::new(0.0, 2.0), Vec2::new(0.0, 4.0)];
+        assert!((merged.get(weightless) - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn median_selects_middle() {
+        let vals = [0.7, 0.1, 0.4, 0.9, 0.3];
+        assert_eq!(Median::<5, ()>(()).merge(vals, &()), 0.4);
+        assert_eq!(MedianIndex::<5, ()>(()).merge(vals, &()), 2);
+        assert_eq!(Median::<5, ()>(()).merge([0.0f32; 0], &()), 0.0);
+
+        // ties keep their original order, so the middle of the three 0.5s is chosen.
+        let ties = [0.5, 0.2, 0.5, 0.9, 0.5];
+        assert_eq!(MedianIndex::<5, ()>(()).merge(ties, &()), 2);
+        // with an even count, the lower middle is chosen.
+        assert_eq!(MedianIndex::<4, ()>(()).merge(ties, &()), 0);
+    }
+
+    #[test]
+    fn smooth_min_approaches_min() {
+        let vals = [0.4, 0.3, 0.9, 0.35];
+        assert_eq!(Merged(SmoothMin { k: 0.0 }).get(vals), 0.3);
+        let mut last_error = f32::INFINITY;
+        for k in [0.5, 0.1, 0.01, 0.001] {
+            let smooth = Merged(SmoothMin { k }).get(vals);
+            let error = 0.3 - smooth;
+            assert!(error >= 0.0 && error <= last_error);
+            last_error = error;
+        }
+        assert!(last_error < 0.001);
+    }
+
+    struct Constant(f32);
+
+    impl NoiseOp<Vec2> for Constant {
+        type Output = f32;
+
+        fn get(&self, _input: Vec2) -> Self::Output {
+            self.0
+        }
+    }
+
+    #[test]
+    fn radial_blend_transitions() {
+        let blend = RadialBlend {
+            center: Vec2::new(10.0, -4.0),
+            inner_radius: 2.0,
+            outer_radius: 6.0,
+            inner: Constant(1.0),
+            outer: Constant(5.0),
+        };
+        assert_eq!(blend.get(Vec2::new(10.0, -4.0)), 1.0);
+        assert_eq!(blend.get(Vec2::new(11.0, -4.0)), 1.0);
+        assert_eq!(blend.get(Vec2::new(10.0, 2.0)), 5.0);
+        assert_eq!(blend.get(Vec2::new(30.0, 2.0)), 5.0);
+        assert!((blend.get(Vec2::new(6.0, -4.0)) - 3.0).abs() < 1e-6);
+        let near = blend.get(Vec2::new(10.0, -1.0));
+        assert!(1.0 < near && near < 3.0);
+    }
+}
@@ -18,6 +18,7 @@ use bevy_math::{
     IVec2,
     IVec3,
     IVec4,
+    Quat,
     U8Vec2,
     U8Vec3,
     U8Vec4,
@@ -37,16 +38,28 @@ use bevy_math::{
 use conversions::NoiseConverter;
 
 pub mod associating;
+pub mod biomes;
+#[cfg(feature = "bevy_color")]
+pub mod color;
 pub mod conversions;
+pub mod dynamic;
 pub mod fbm;
 pub mod grid;
+pub mod heightfield;
 pub mod mapping;
 pub mod merging;
 pub mod norm;
 pub mod nudges;
 pub mod perlin;
+pub mod presets;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod render;
 pub mod seeded;
+pub mod shaping;
+pub mod simplex;
 pub mod smoothing;
+pub mod tiling;
 pub mod voronoi;
 pub mod white;
 
@@ -125,21 +138,118 @@ where
     fn sample_cold<C: NoiseConverter<Self::Input, Input = C>>(&self, input: C) -> Self::Output {
         self.sample::<C>(input)
     }
+
+    /// samples the noise at each of the `inputs`, writing the results to the same index of `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `out` have different lengths.
+    #[inline]
+    fn sample_many<C: NoiseConverter<Self::Input, Input = C> + Copy>(
+        &self,
+        inputs: &[C],
+        out: &mut [Self::Output],
+    ) {
+        assert_eq!(inputs.len(), out.len());
+        for (input, out) in inputs.iter().zip(out) {
+            *out = self.sample(*input);
+        }
+    }
+
+    /// samples the noise on a `width` by `height` lattice starting at `origin` and moving by `step`
+    /// between samples, writing the results to `out` row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is not `width * height` long.
+    #[inline]
+    fn sample_grid_2d(
+        &self,
+        origin: Vec2,
+        step: Vec2,
+        width: usize,
+        height: usize,
+        out: &mut [Self::Output],
+    ) where
+        Vec2: NoiseConverter<Self::Input, Input = Vec2>,
+    {
+        assert_eq!(width * height, out.len());
+        if width == 0 {
+            return;
+        }
+        for (y, row) in out.chunks_exact_mut(width).enumerate() {
+            for (x, out) in row.iter_mut().enumerate() {
+                *out = self.sample(origin + step * Vec2::new(x as f32, y as f32));
+            }
+        }
+    }
+
+    /// The same as [`sample_grid_2d`](Self::sample_grid_2d), but the rows are sampled in parallel
+    /// with rayon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is not `width * height` long.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn sample_grid_2d_par(
+        &self,
+        origin: Vec2,
+        step: Vec2,
+        width: usize,
+        height: usize,
+        out: &mut [Self::Output],
+    ) where
+        Self: Sync,
+        Self::Output: Send,
+        Vec2: NoiseConverter<Self::Input, Input = Vec2>,
+    {
+        use rayon::{
+            iter::{
+                IndexedParallelIterator,
+                ParallelIterator,
+            },
+            slice::ParallelSliceMut,
+        };
+
+        assert_eq!(width * height, out.len());
+        if width == 0 {
+            return;
+        }
+        out.par_chunks_exact_mut(width)
+            .enumerate()
+            .for_each(|(y, row)| {
+                for (x, out) in row.iter_mut().enumerate() {
+                    *out = self.sample(origin + step * Vec2::new(x as f32, y as f32));
+                }
+            });
+    }
 }
 
 /// Represents period at which the noise may repeat, the inverse of frequency.
 /// You can think of this like the scale of the noise.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct Period(pub f32);
 
 /// This describes typical settings for a [`NoiseOp`] that creates noise based purely on space and
 /// seeds.
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct SpatialNoiseSettings {
     /// The period of the noise.
     pub period: Period,
+    // the rng changes as it is used, so it isn't reflected. Reflected settings start at seed 0.
+    #[cfg_attr(feature = "reflect", reflect(ignore, default = "reflected_rng"))]
     rng: NoiseRng<White32>,
 }
 
+/// The rng of [`SpatialNoiseSettings`] made from reflection.
+#[cfg(feature = "reflect")]
+fn reflected_rng() -> NoiseRng<White32> {
+    NoiseRng::new_seed(0)
+}
+
 impl SpatialNoiseSettings {
     /// Constructs a new [`SpatialNoiseSettings`] based on the given seed and
     /// [`period`](SpatialNoiseSettings::period).
@@ -180,8 +290,44 @@ impl SpatialNoiseSettings {
     }
 }
 
+/// The serialized form of [`SpatialNoiseSettings`]. The rng is stored as its seed.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "SpatialNoiseSettings")]
+struct SpatialNoiseSettingsData {
+    seed: u64,
+    period: Period,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SpatialNoiseSettings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(
+            &SpatialNoiseSettingsData {
+                seed: self.rng.current_seed(),
+                period: self.period,
+            },
+            serializer,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SpatialNoiseSettings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = <SpatialNoiseSettingsData as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Self {
+            period: data.period,
+            rng: NoiseRng::new_seed(data.seed),
+        })
+    }
+}
+
 // built in
 impl<T: NoiseType, const N: usize> NoiseType for [T; N] {}
+impl<A: NoiseType, B: NoiseType> NoiseType for (A, B) {}
+impl<T: NoiseType> NoiseType for Option<T> {}
+impl NoiseType for bool {}
 impl NoiseType for f32 {}
 impl NoiseType for f64 {}
 impl NoiseType for u8 {}
@@ -205,6 +351,7 @@ impl NoiseType for DVec3 {}
 impl NoiseType for Dir3 {}
 impl NoiseType for Vec4 {}
 impl NoiseType for DVec4 {}
+impl NoiseType for Quat {}
 impl NoiseType for I8Vec2 {}
 impl NoiseType for I8Vec3 {}
 impl NoiseType for I8Vec4 {}
@@ -253,9 +400,21 @@ mod tests {
             SeedOf,
             Seeding,
         },
+        smoothing::{
+            Lerp,
+            LerpValuesOf,
+            Smooth,
+        },
         *,
     };
-    use crate as noiz;
+    use crate::{
+        self as noiz,
+        spatial::interpolating::{
+            Cubic,
+            Linear,
+            MixerFxn,
+        },
+    };
 
     // this is taken from the docs for noise_op.
     noise_op! {
@@ -295,4 +454,161 @@ mod tests {
         });
         let _test_res = noise.sample(Vec2::ONE);
     }
+
+    noise_op! {
+        /// Gives the left half of each cell a random value and the right half its x coordinate.
+        pub struct SplitCells for Vec2 -> UNorm =
+        pub struct SplitCellsArgs {seed: u32, period: f32,}
+        impl
+        fn GridNoise = GridNoise::new_period(period);
+        // 'match' runs the operations of the first arm whose condition is true for the input.
+        match |point: GridPoint2| {
+            point.offset.x < 0.5 => {
+                fn Seeding = Seeding(seed);
+                fn SeedOf;
+            }
+            _ => |point: GridPoint2| point.base.x, // a single operation doesn't need braces.
+        }
+        as UNorm
+    }
+
+    #[test]
+    fn match_picks_arm() {
+        let noise = SplitCells::from(SplitCellsArgs {
+            seed: 8,
+            period: 2.0,
+        });
+        let grid = GridNoise::new_period(2.0);
+        for x in [0.5, 1.5, 2.2, 3.9] {
+            let input = Vec2::new(x, 0.7);
+            let point = grid.get(input);
+            let expected = if point.offset.x < 0.5 {
+                SeedOf.get(Seeding(8).get(point))
+            } else {
+                point.base.x
+            };
+            assert_eq!(noise.sample(input), expected.adapt::<UNorm>());
+        }
+    }
+
+    noise_op! {
+        /// Value noise that is generic over the curve it is smoothed with.
+        pub struct SmoothValue<C> for Vec2 -> f32 where C: MixerFxn<f32, f32> + Default =
+        pub struct SmoothValueArgs {seed: u32, period: f32,}
+        impl
+        fn GridNoise = GridNoise::new_period(period);
+        fn Lerp;
+        mut LerpValuesOf for fn Seeding = Seeding(seed);
+        mut LerpValuesOf for fn SeedOf;
+        mut LerpValuesOf for as UNorm, f32;
+        fn smooth: Smooth<C> = Smooth(C::default());
+    }
+
+    #[test]
+    fn generic_noise_is_constructable() {
+        let args = || SmoothValueArgs {
+            seed: 4,
+            period: 8.0,
+        };
+        let cubic = SmoothValue::<Cubic>::from(args());
+        let linear = SmoothValue::<Linear>::from(args());
+        // on the grid lines, the curve doesn't matter.
+        let on_grid = Vec2::new(16.0, 8.0);
+        assert!((cubic.sample(on_grid) - linear.sample(on_grid)).abs() < 1e-5);
+        assert_ne!(
+            cubic.sample(Vec2::new(13.0, 7.0)),
+            linear.sample(Vec2::new(13.0, 7.0))
+        );
+    }
+
+    noise_op! {
+        /// Value noise that can optionally offset its seed.
+        pub struct OffsetValue for Vec2 -> UNorm =
+        pub struct OffsetValueArgs {seed: u32, period: f32, offset: bool,}
+        impl
+        const let mut seed = seed;
+        // 'const if' runs only during construction, so it can change how the noise is set up.
+        const if offset {
+            seed = seed.wrapping_add(1);
+        }
+        fn GridNoise = GridNoise::new_period(period);
+        fn Seeding = Seeding(seed);
+        fn SeedOf;
+        as UNorm
+    }
+
+    #[test]
+    fn const_if_branches_construction() {
+        let args = |seed, offset| OffsetValueArgs {
+            seed,
+            period: 4.0,
+            offset,
+        };
+        let plain = OffsetValue::from(args(5, false));
+        let offset = OffsetValue::from(args(5, true));
+        let shifted = OffsetValue::from(args(6, false));
+        for x in 0..10 {
+            let input = Vec2::new(x as f32 * 2.3, 1.0);
+            assert_eq!(offset.sample(input), shifted.sample(input));
+        }
+        assert_ne!(plain.sample(Vec2::ONE), offset.sample(Vec2::ONE));
+    }
+
+    #[test]
+    fn grid_sampling_matches_sample() {
+        let noise = MyNoise::from(MyNoiseArgs {
+            seed: 3,
+            period: 4.0,
+        });
+        let origin = Vec2::new(-3.0, 1.5);
+        let step = Vec2::new(0.7, 1.3);
+        let mut grid = [UNorm::new_clamped(0.0); 6 * 4];
+        noise.sample_grid_2d(origin, step, 6, 4, &mut grid);
+        for y in 0..4 {
+            for x in 0..6 {
+                let expected = noise.sample(origin + step * Vec2::new(x as f32, y as f32));
+                assert_eq!(grid[x + y * 6], expected);
+            }
+        }
+
+        let inputs = [Vec2::ZERO, Vec2::new(3.0, -2.0), Vec2::splat(9.1)];
+        let mut many = [UNorm::new_clamped(0.0); 3];
+        noise.sample_many(&inputs, &mut many);
+        for (input, result) in inputs.into_iter().zip(many) {
+            assert_eq!(result, noise.sample(input));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_grid_sampling_matches_serial() {
+        let noise = MyNoise::from(MyNoiseArgs {
+            seed: 5,
+            period: 7.0,
+        });
+        let origin = Vec2::new(12.0, -40.0);
+        let step = Vec2::splat(0.37);
+        let mut serial = vec![UNorm::new_clamped(0.0); 64 * 48];
+        let mut parallel = serial.clone();
+        noise.sample_grid_2d(origin, step, 64, 48, &mut serial);
+        noise.sample_grid_2d_par(origin, step, 64, 48, &mut parallel);
+        assert!(
+            serial
+                .iter()
+                .zip(&parallel)
+                .all(|(a, b)| a.adapt::<f32>().to_bits() == b.adapt::<f32>().to_bits())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spatial_settings_round_trip() {
+        let mut settings = SpatialNoiseSettings::new(987_654_321_012, 12.0);
+        settings.rand_32();
+        let json = serde_json::to_string(&settings).unwrap();
+        let mut back: SpatialNoiseSettings = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.period, settings.period);
+        assert_eq!(back.rand_32(), settings.rand_32());
+        assert_eq!(back.rand_32(), settings.rand_32());
+    }
 }
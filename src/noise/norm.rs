@@ -1,11 +1,18 @@
 //! This module facilatites scalar noise results
 
+use core::ops::{
+    Add,
+    Mul,
+    Sub,
+};
+
 use bevy_math::Curve;
 
 use super::{
     NoiseType,
     conversions::convertible,
 };
+use crate::spatial::interpolating::Lerpable;
 
 /// A value that stores an f32 in range (-1, 0)∪(0, 1).
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -298,6 +305,12 @@ impl UNorm {
         Self(lo.0 + (hi.0 - lo.0) * self.0)
     }
 
+    /// Linearly interpolates from this value to `other` by `t`.
+    #[inline]
+    pub fn lerp(self, other: Self, t: UNorm) -> Self {
+        Self::new_clamped(self.0.lerp_dirty(other.0, t.0))
+    }
+
     /// populates a u8 based on this value
     #[inline]
     pub fn fill_u8(self) -> u8 {
@@ -342,6 +355,64 @@ convertible!(UNorm = SNorm, |source| UNorm::map_to_snorm(source));
 impl NoiseType for SNorm {}
 impl NoiseType for UNorm {}
 
+/// implements arithmetic operators that clamp the result for norms
+macro_rules! impl_norm_ops {
+    ($t:ty) => {
+        impl Add for $t {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::new_clamped(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self::new_clamped(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self::new_clamped(self.0 * rhs)
+            }
+        }
+    };
+}
+
+impl_norm_ops!(SNorm);
+impl_norm_ops!(UNorm);
+
+impl Mul for UNorm {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new_clamped(self.0 * rhs.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for UNorm {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UNorm {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <f32 as serde::Deserialize>::deserialize(deserializer).map(Self::new_clamped)
+    }
+}
+
 /// forces the f32 to be nonzero by forcing on the least significant bit.
 #[inline]
 pub const fn make_nonzero_f32(v: f32) -> f32 {
@@ -362,4 +433,38 @@ mod tests {
     fn test_non_zero() {
         assert_ne!(0f32, make_nonzero_f32(0.0));
     }
+
+    #[test]
+    fn operators_clamp() {
+        for value in [0.1, 0.5, 0.9] {
+            let unorm = UNorm::new_clamped(value);
+            let snorm = SNorm::new_clamped(-value);
+            assert_eq!(unorm * 1.0, unorm);
+            assert_eq!(snorm * 1.0, snorm);
+            assert_eq!(
+                unorm * UNorm::new_clamped(1.0),
+                UNorm::new_clamped(value * UNorm::MAX)
+            );
+        }
+
+        let high = UNorm::new_clamped(0.8);
+        let low = UNorm::new_clamped(0.3);
+        assert_eq!(high + high, UNorm::new_clamped(1.0));
+        assert_eq!(low - high, UNorm::new_clamped(0.0));
+        assert_eq!(high * -2.0, UNorm::new_clamped(0.0));
+        assert_eq!((high - low).adapt::<f32>(), 0.8 - 0.3);
+
+        let snorm = SNorm::new_clamped(0.75);
+        assert_eq!(snorm + snorm, SNorm::new_clamped(1.0));
+        assert_eq!(
+            -snorm.adapt::<f32>(),
+            (SNorm::new_clamped(0.0) - snorm).adapt::<f32>()
+        );
+        assert_eq!(snorm * -4.0, SNorm::new_clamped(-1.0));
+
+        assert_eq!(
+            low.lerp(high, UNorm::new_clamped(0.5)),
+            UNorm::new_clamped(0.55)
+        );
+    }
 }
@@ -1,6 +1,7 @@
-//! This module allows worly noise to be created
+//! This module allows worley noise to be created
 
 use bevy_math::{
+    IVec2,
     UVec2,
     UVec3,
     UVec4,
@@ -21,11 +22,17 @@ use super::{
         SNorm,
         UNorm,
     },
-    seeded::Seeded,
+    seeded::{
+        Seeded,
+        Seeding,
+    },
 };
+use crate::spatial::square::flatten2d;
 
 /// Offsets a grid point randomly, with respect to its surroundings.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
 pub struct Nudge<const RESTRICT_POSITIVE: bool = false> {
     /// the amount the grid point can move
     multiplier: f32,
@@ -68,6 +75,90 @@ impl<const RESTRICT_POSITIVE: bool> Nudge<RESTRICT_POSITIVE> {
     }
 }
 
+/// Places a point in each grid cell like a [`Nudge`], but then relaxes it against its neighbors'
+/// points, similar to Lloyd relaxation. This produces points that are more evenly spaced.
+///
+/// Each iteration moves every point halfway to the mean of its 8 neighbors. Since neighbors move
+/// too, the cost grows with the square of the iterations, so they are capped at
+/// [`MAX_ITERATIONS`](Self::MAX_ITERATIONS).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelaxedPoint {
+    seeder: Seeding,
+    nudge: Nudge<true>,
+    iterations: u32,
+}
+
+impl RelaxedPoint {
+    /// The most relaxation iterations that can be done.
+    pub const MAX_ITERATIONS: u32 = 4;
+    /// The length of one side of the window of cells needed for the maximum iterations.
+    const WINDOW: usize = Self::MAX_ITERATIONS as usize * 2 + 3;
+
+    /// Creates a new [`RelaxedPoint`] where points are nudged by up to `range` before being
+    /// relaxed `iterations` times.
+    pub fn new(range: f32, seed: u32, iterations: u32) -> Self {
+        Self {
+            seeder: Seeding(seed),
+            nudge: Nudge::new_magnitude(range.abs().min(1.0)),
+            iterations: iterations.min(Self::MAX_ITERATIONS),
+        }
+    }
+
+    /// The number of relaxation iterations being done.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+}
+
+impl NoiseOp<GridPoint2> for RelaxedPoint {
+    type Output = Seeded<Vec2>;
+
+    /// Gets the relaxed point of the cell `input` is in, relative to the cell's base.
+    #[inline]
+    fn get(&self, input: GridPoint2) -> Self::Output {
+        let radius = self.iterations as i32 + 1;
+        let center = Self::WINDOW as i32 / 2;
+        let index = |d: IVec2| {
+            flatten2d::<{ Self::WINDOW }>((center + d.x) as usize, (center + d.y) as usize)
+        };
+
+        let mut points = [Vec2::ZERO; Self::WINDOW * Self::WINDOW];
+        let mut relaxed = points;
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                let d = IVec2::new(x, y);
+                let seeded = self.seeder.get(input.base.wrapping_add_signed(d));
+                points[index(d)] = d.as_vec2() + self.nudge.get(seeded).value;
+            }
+        }
+
+        for iteration in 0..self.iterations as i32 {
+            let reach = radius - 1 - iteration;
+            for y in -reach..=reach {
+                for x in -reach..=reach {
+                    let d = IVec2::new(x, y);
+                    let mut neighbors = Vec2::ZERO;
+                    for ny in -1..=1 {
+                        for nx in -1..=1 {
+                            if nx != 0 || ny != 0 {
+                                neighbors += points[index(d + IVec2::new(nx, ny))];
+                            }
+                        }
+                    }
+                    let point = points[index(d)];
+                    relaxed[index(d)] = point + (neighbors / 8.0 - point) * 0.5;
+                }
+            }
+            core::mem::swap(&mut points, &mut relaxed);
+        }
+
+        Seeded {
+            value: points[index(IVec2::ZERO)],
+            meta: self.seeder.get(input.base).meta,
+        }
+    }
+}
+
 /// easily implements nudging for different types
 macro_rules! impl_nudge {
     ($vec:path, $uvec:path, $point:path, $d:literal, $u2f:ident) => {
@@ -107,3 +198,60 @@ macro_rules! impl_nudge {
 impl_nudge!(Vec2, UVec2, GridPoint2, 2.0, as_vec2);
 impl_nudge!(Vec3, UVec3, GridPoint3, 3.0, as_vec3);
 impl_nudge!(Vec4, UVec4, GridPoint4, 4.0, as_vec4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nearest_neighbor_variance(noise: &RelaxedPoint) -> f32 {
+        let size = 16;
+        let points = (0..size * size)
+            .map(|i| {
+                let cell = UVec2::new(i % size, i / size) + UVec2::splat(1000);
+                let point = GridPoint2 {
+                    base: cell,
+                    offset: Vec2::ZERO,
+                };
+                cell.as_vec2() + noise.get(point).value
+            })
+            .collect::<Vec<_>>();
+        let mut distances = Vec::new();
+        for y in 1..size - 1 {
+            for x in 1..size - 1 {
+                let point = points[(x + y * size) as usize];
+                let mut nearest = f32::INFINITY;
+                for (nx, ny) in [
+                    (0, 1),
+                    (2, 1),
+                    (1, 0),
+                    (1, 2),
+                    (0, 0),
+                    (2, 2),
+                    (0, 2),
+                    (2, 0),
+                ] {
+                    let other = points[(x + nx - 1 + (y + ny - 1) * size) as usize];
+                    nearest = nearest.min(point.distance(other));
+                }
+                distances.push(nearest);
+            }
+        }
+        let mean = distances.iter().sum::<f32>() / distances.len() as f32;
+        distances
+            .iter()
+            .map(|d| (d - mean) * (d - mean))
+            .sum::<f32>()
+            / distances.len() as f32
+    }
+
+    #[test]
+    fn relaxing_evens_spacing() {
+        let raw = RelaxedPoint::new(1.0, 8, 0);
+        let relaxed = RelaxedPoint::new(1.0, 8, 3);
+        assert!(nearest_neighbor_variance(&relaxed) < nearest_neighbor_variance(&raw));
+        assert_eq!(
+            RelaxedPoint::new(1.0, 8, 100).iterations(),
+            RelaxedPoint::MAX_ITERATIONS
+        );
+    }
+}
@@ -34,10 +34,28 @@ pub unsafe trait PerlinSource<I: NoiseType> {
     fn get_perlin_dot(&self, seed: u32, offset: I) -> f32;
 }
 
+/// A [`PerlinSource`] that can also give its direction vectors, which allows use as `S` in
+/// [`PerlinWithGradient`].
+pub trait PerlinGradientSource<I: NoiseType>: PerlinSource<I> {
+    /// Gets the gradient of [`get_perlin_dot`](PerlinSource::get_perlin_dot) for this seed with
+    /// respect to the offset. Since the dot product is linear in the offset, this is just the
+    /// direction vector for the seed.
+    fn get_perlin_gradient(&self, seed: u32) -> I;
+}
+
 /// A simple perlin noise implementation where `S` is the source of the direction vectors.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Perlin<S>(pub S);
 
+/// Like [`Perlin`], but also gives the gradient of the result with respect to the offset.
+///
+/// This is only the gradient of one corner's contribution. To get the gradient of smoothed perlin
+/// noise, interpolate these gradients as values, and then add the gradient from the interpolation
+/// itself, which is the gradient part of `interpolate_and_gradient_2d` (or `_3d`, `_4d`) on the
+/// corner values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PerlinWithGradient<S>(pub S);
+
 macro_rules! impl_perlin {
     ($vec:ty, $sqrt_d:expr) => {
         impl<S: PerlinSource<$vec>> NoiseOp<Seeded<$vec>> for Perlin<S> {
@@ -49,9 +67,22 @@ macro_rules! impl_perlin {
                 dot * S::NORMALIZING_FACTOR / $sqrt_d
             }
         }
+
+        impl<S: PerlinGradientSource<$vec>> NoiseOp<Seeded<$vec>> for PerlinWithGradient<S> {
+            type Output = (f32, $vec);
+
+            #[inline]
+            fn get(&self, input: Seeded<$vec>) -> Self::Output {
+                let scale = S::NORMALIZING_FACTOR / $sqrt_d;
+                let dot = self.0.get_perlin_dot(input.seed(), input.value);
+                let gradient = self.0.get_perlin_gradient(input.seed());
+                (dot * scale, gradient * scale)
+            }
+        }
     };
 }
 
+impl_perlin!(f32, 1.0);
 impl_perlin!(Vec2, core::f32::consts::SQRT_2);
 impl_perlin!(Vec3, 1.7320508); // sqrt 3
 impl_perlin!(Vec4, 2.0); // sqrt 4
@@ -60,6 +91,28 @@ impl_perlin!(Vec4, 2.0); // sqrt 4
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct RuntimeRand;
 
+// SAFETY: The gradient is ±1, so the product is within the offset, and the offset is taken care of
+// by setting `NORMALIZING_FACTOR` to 2.0.
+unsafe impl PerlinSource<f32> for RuntimeRand {
+    const NORMALIZING_FACTOR: f32 = 2.0;
+
+    #[inline]
+    fn get_perlin_dot(&self, seed: u32, offset: f32) -> f32 {
+        PerlinGradientSource::<f32>::get_perlin_gradient(self, seed) * offset
+    }
+}
+
+impl PerlinGradientSource<f32> for RuntimeRand {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> f32 {
+        if White32(seed).get(0) & 1 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
 // SAFETY: The dot product can not be grater than the product of the
 // lengths, and one length is normalized and the other one is taken care of by setting
 // `NORMALIZING_FACTOR` to 2.0.
@@ -68,11 +121,18 @@ unsafe impl PerlinSource<Vec2> for RuntimeRand {
 
     #[inline]
     fn get_perlin_dot(&self, seed: u32, offset: Vec2) -> f32 {
+        PerlinGradientSource::<Vec2>::get_perlin_gradient(self, seed).dot(offset)
+    }
+}
+
+impl PerlinGradientSource<Vec2> for RuntimeRand {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec2 {
         let vec = Vec2::new(
             convert!(White32(seed).get(0) => SNorm, f32),
             convert!(White32(seed).get(1) => SNorm, f32),
         ) * 128.0; // extra multiplication prevenst len from being Nan because of an approx zero length.
-        vec.normalize().dot(offset)
+        vec.normalize()
     }
 }
 
@@ -82,12 +142,19 @@ unsafe impl PerlinSource<Vec3> for RuntimeRand {
 
     #[inline]
     fn get_perlin_dot(&self, seed: u32, offset: Vec3) -> f32 {
+        PerlinGradientSource::<Vec3>::get_perlin_gradient(self, seed).dot(offset)
+    }
+}
+
+impl PerlinGradientSource<Vec3> for RuntimeRand {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec3 {
         let vec = Vec3::new(
             convert!(White32(seed).get(0) => SNorm, f32),
             convert!(White32(seed).get(1) => SNorm, f32),
             convert!(White32(seed).get(2) => SNorm, f32),
         ) * 128.0; // extra multiplication prevenst len from being Nan because of an approx zero length.
-        vec.normalize().dot(offset)
+        vec.normalize()
     }
 }
 
@@ -97,13 +164,48 @@ unsafe impl PerlinSource<Vec4> for RuntimeRand {
 
     #[inline]
     fn get_perlin_dot(&self, seed: u32, offset: Vec4) -> f32 {
+        PerlinGradientSource::<Vec4>::get_perlin_gradient(self, seed).dot(offset)
+    }
+}
+
+impl PerlinGradientSource<Vec4> for RuntimeRand {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec4 {
         let vec = Vec4::new(
             convert!(White32(seed).get(0) => SNorm, f32),
             convert!(White32(seed).get(1) => SNorm, f32),
             convert!(White32(seed).get(2) => SNorm, f32),
             convert!(White32(seed).get(3) => SNorm, f32),
         ) * 128.0; // extra multiplication prevenst len from being Nan because of an approx zero length.
-        vec.normalize().dot(offset)
+        vec.normalize()
+    }
+}
+
+/// A perlin noise source that picks gradients with a user supplied function.
+/// The function is given the seed of the grid point and should return a unit vector for it.
+///
+/// Returning a unit vector is the only contract `F` must uphold. This is checked: the result is
+/// normalized before use, and a zero or non-finite result is treated as no gradient at all. That
+/// keeps this safe to use with any closure, but for best results, `F` should already return
+/// normalized vectors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CustomGradient<F>(pub F);
+
+// SAFETY: The gradient is normalized or zero, so the dot product can not be grater than the length
+// of the offset, which is taken care of by setting `NORMALIZING_FACTOR` to 2.0.
+unsafe impl<F: Fn(u32) -> Vec2> PerlinSource<Vec2> for CustomGradient<F> {
+    const NORMALIZING_FACTOR: f32 = 2.0;
+
+    #[inline]
+    fn get_perlin_dot(&self, seed: u32, offset: Vec2) -> f32 {
+        PerlinGradientSource::<Vec2>::get_perlin_gradient(self, seed).dot(offset)
+    }
+}
+
+impl<F: Fn(u32) -> Vec2> PerlinGradientSource<Vec2> for CustomGradient<F> {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec2 {
+        (self.0)(seed).normalize_or_zero()
     }
 }
 
@@ -111,6 +213,24 @@ unsafe impl PerlinSource<Vec4> for RuntimeRand {
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Hashed;
 
+// SAFETY: The gradient is ±1, so the product is within the offset, which is normalized by setting
+// `NORMALIZING_FACTOR` to 1.0.
+unsafe impl PerlinSource<f32> for Hashed {
+    const NORMALIZING_FACTOR: f32 = 1.0;
+
+    #[inline]
+    fn get_perlin_dot(&self, seed: u32, offset: f32) -> f32 {
+        PerlinGradientSource::<f32>::get_perlin_gradient(self, seed) * offset
+    }
+}
+
+impl PerlinGradientSource<f32> for Hashed {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> f32 {
+        if seed & 1 == 0 { 1.0 } else { -1.0 }
+    }
+}
+
 // SAFETY: The dot product can not be grater than the product of the
 // lengths, and one length is within √d. So their product is normalized by setting
 // `NORMALIZING_FACTOR` to 1.0.
@@ -135,6 +255,13 @@ unsafe impl PerlinSource<Vec2> for Hashed {
     }
 }
 
+impl PerlinGradientSource<Vec2> for Hashed {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec2 {
+        Vec2::from_array(Vec2::AXES.map(|axis| self.get_perlin_dot(seed, axis)))
+    }
+}
+
 // SAFETY: impl PerlinSource<Vec2> for Cardinal.
 unsafe impl PerlinSource<Vec3> for Hashed {
     const NORMALIZING_FACTOR: f32 = 1.0;
@@ -164,6 +291,13 @@ unsafe impl PerlinSource<Vec3> for Hashed {
     }
 }
 
+impl PerlinGradientSource<Vec3> for Hashed {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec3 {
+        Vec3::from_array(Vec3::AXES.map(|axis| self.get_perlin_dot(seed, axis)))
+    }
+}
+
 // SAFETY: impl PerlinSource<Vec2> for Cardinal.
 unsafe impl PerlinSource<Vec4> for Hashed {
     const NORMALIZING_FACTOR: f32 = 1.0;
@@ -198,3 +332,91 @@ unsafe impl PerlinSource<Vec4> for Hashed {
         result
     }
 }
+
+impl PerlinGradientSource<Vec4> for Hashed {
+    #[inline]
+    fn get_perlin_gradient(&self, seed: u32) -> Vec4 {
+        Vec4::from_array(Vec4::AXES.map(|axis| self.get_perlin_dot(seed, axis)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise::{
+            grid::GridNoise,
+            seeded::{
+                Seed,
+                Seeding,
+            },
+            smoothing::{
+                Lerp,
+                LerpValues,
+                Smooth,
+            },
+        },
+        spatial::interpolating::Cubic,
+    };
+
+    #[test]
+    fn custom_gradient_uses_closure() {
+        let perlin = Perlin(CustomGradient(|seed: u32| match seed & 3 {
+            0 => Vec2::X,
+            1 => Vec2::NEG_X,
+            2 => Vec2::Y * 5.0,
+            _ => Vec2::ZERO,
+        }));
+        let offset = Vec2::new(0.5, -0.25);
+        let expected = [0.5, -0.5, -0.25, 0.0];
+        for (seed, expected) in expected.into_iter().enumerate() {
+            let value = perlin.get(Seeded {
+                value: offset,
+                meta: Seed(seed as u32),
+            });
+            let expected = expected * 2.0 / core::f32::consts::SQRT_2;
+            assert!((value - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn perlin_1d_is_zero_on_grid() {
+        let grid = GridNoise::new_frequency(1.0);
+        let noise = |x: f32| {
+            let ready =
+                Lerp.get(grid.get(x)).map(|LerpValues(ends)| {
+                    LerpValues(ends.map(|end| {
+                        Perlin(RuntimeRand).get(Seeding(2).get(end).map(|end| end.offset))
+                    }))
+                });
+            Smooth(Cubic).get(ready)
+        };
+        for x in -5..5 {
+            assert!(noise(x as f32).abs() < 1e-6);
+            assert!((-1.0..=1.0).contains(&noise(x as f32 + 0.5)));
+        }
+    }
+
+    #[test]
+    fn gradient_matches_finite_difference() {
+        let step = Vec3::new(1e-3, -2e-3, 1.5e-3);
+        for seed in 0..16 {
+            let at = |value: Vec3| Seeded {
+                value,
+                meta: Seed(seed),
+            };
+            let offset = Vec3::new(0.3, -0.6, 0.1);
+            for source in [
+                &PerlinWithGradient(RuntimeRand)
+                    as &dyn NoiseOp<Seeded<Vec3>, Output = (f32, Vec3)>,
+                &PerlinWithGradient(Hashed),
+            ] {
+                let (value, gradient) = source.get(at(offset));
+                let (next, _) = source.get(at(offset + step));
+                assert!((next - value - gradient.dot(step)).abs() < 1e-5);
+            }
+            let value = PerlinWithGradient(RuntimeRand).get(at(offset)).0;
+            assert!((value - Perlin(RuntimeRand).get(at(offset))).abs() < 1e-6);
+        }
+    }
+}
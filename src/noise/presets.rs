@@ -0,0 +1,550 @@
+//! This module contains ready-made noise for common uses, built from [`SpatialNoiseSettings`].
+
+use bevy_math::{
+    UVec2,
+    UVec3,
+    Vec2,
+    Vec3,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    Period,
+    SpatialNoiseSettings,
+    conversions::NoiseConverter,
+    fbm::{
+        Accumulator,
+        Octave,
+        OctaveSumAccumulator,
+        PostAccumulator,
+        Settings,
+        StandardFbm,
+        StandardOctave,
+        WeightedOctave,
+    },
+    grid::{
+        GridNoise,
+        GridPoint2,
+        WrappingGridNoise,
+    },
+    merging::EuclideanDistance,
+    norm::{
+        SNorm,
+        UNorm,
+    },
+    perlin::{
+        Perlin,
+        RuntimeRand,
+    },
+    seeded::Seeding,
+    smoothing::{
+        Lerp,
+        LerpValues,
+        Smooth,
+    },
+    voronoi::{
+        Voronoi,
+        Worley,
+        worley_mode::Nearest,
+    },
+};
+use crate::spatial::interpolating::Cubic;
+
+/// Standard 2d perlin noise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerlinNoise {
+    grid: GridNoise,
+    seeding: Seeding,
+}
+
+impl PerlinNoise {
+    /// Constructs a new [`PerlinNoise`] from its settings.
+    pub fn new(mut args: SpatialNoiseSettings) -> Self {
+        Self {
+            grid: args.period.into(),
+            seeding: args.seeding(),
+        }
+    }
+}
+
+impl From<SpatialNoiseSettings> for PerlinNoise {
+    fn from(value: SpatialNoiseSettings) -> Self {
+        Self::new(value)
+    }
+}
+
+impl NoiseOp<Vec2> for PerlinNoise {
+    type Output = SNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        SNorm::new_clamped(perlin_at(self.seeding, self.grid.get(input), |base| base))
+    }
+}
+
+/// Samples perlin noise at `point`, passing each corner's base through `wrap` before seeding it.
+#[inline]
+fn perlin_at(seeding: Seeding, point: GridPoint2, wrap: impl Fn(UVec2) -> UVec2) -> f32 {
+    let ready = Lerp.get(point).map(|LerpValues(corners)| {
+        LerpValues(corners.map(|mut corner| {
+            corner.base = wrap(corner.base);
+            Perlin(RuntimeRand).get(seeding.get(corner).map(|point| point.offset))
+        }))
+    });
+    Smooth(Cubic).get(ready)
+}
+
+/// Fast, approximate 2d worley noise: the distance to the nearest cell point.
+/// Only the 4 corners around each sample are checked instead of the full 3x3 surroundings, which is
+/// about half as many candidate points. To keep that mostly accurate, points are only nudged within
+/// half of each cell, so the cells look more grid-like than full worley noise. The result is exact
+/// near the middle of each cell, but near the edges, the nearest point can be missed.
+#[derive(Debug, Clone)]
+pub struct FastWorley {
+    grid: GridNoise,
+    voronoi: Voronoi<2, Worley<EuclideanDistance, Nearest>, true>,
+}
+
+impl FastWorley {
+    /// Constructs a new [`FastWorley`] from its settings.
+    pub fn new(mut args: SpatialNoiseSettings) -> Self {
+        Self {
+            grid: args.period.into(),
+            voronoi: Voronoi::full(
+                args.rand_32(),
+                Worley {
+                    // the nearest corner can be up to a diagonal away.
+                    expected_length_multiplier: core::f32::consts::SQRT_2,
+                    ..Default::default()
+                },
+            ),
+        }
+    }
+}
+
+impl From<SpatialNoiseSettings> for FastWorley {
+    fn from(value: SpatialNoiseSettings) -> Self {
+        Self::new(value)
+    }
+}
+
+impl NoiseOp<Vec2> for FastWorley {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        self.voronoi.get(self.grid.get(input))
+    }
+}
+
+/// 3d worley noise that tiles seamlessly on all three axes, for volumetric clouds or textures.
+/// The volume repeats every `tiles` cells, each of which is the settings' period wide.
+#[derive(Debug, Clone)]
+pub struct SeamlessWorley3d {
+    grid: WrappingGridNoise<UVec3>,
+    voronoi: Voronoi<3, Worley<EuclideanDistance, Nearest>>,
+}
+
+impl SeamlessWorley3d {
+    /// Constructs a new [`SeamlessWorley3d`] from its settings, repeating every `tiles` cells.
+    pub fn new(mut args: SpatialNoiseSettings, tiles: UVec3) -> Self {
+        Self {
+            grid: WrappingGridNoise::new_tiling(args.period.0, tiles),
+            voronoi: Voronoi::full_default(args.rand_32()),
+        }
+    }
+}
+
+impl NoiseOp<Vec3> for SeamlessWorley3d {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec3) -> Self::Output {
+        self.voronoi.get(self.grid.get(input))
+    }
+}
+
+/// Mountainous terrain made of ridged perlin fbm with a warped domain.
+/// Before sampling the ridges, the input is offset by two low frequency [`PerlinNoise`]s, which
+/// bends the ridges into more natural shapes. Each of the `OCTAVES` octaves is ridged by taking
+/// `1 - |perlin|` and squaring it to sharpen the peaks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarpedRidgedTerrain<const OCTAVES: usize = 6> {
+    warp: [PerlinNoise; 2],
+    warp_strength: f32,
+    octaves: [(PerlinNoise, WeightedOctave); OCTAVES],
+}
+
+impl<const OCTAVES: usize> WarpedRidgedTerrain<OCTAVES> {
+    /// Constructs a new [`WarpedRidgedTerrain`] from its settings.
+    /// `warp_strength` is how far the input can be warped, relative to the period.
+    pub fn new(mut args: SpatialNoiseSettings, warp_strength: f32) -> Self {
+        let period = args.period;
+        let warp = [(); 2].map(|_| PerlinNoise::new(args.branch()));
+
+        let mut fbm = StandardFbm::new(period, 0.5, 0.5);
+        let octaves: [StandardOctave; OCTAVES] = core::array::from_fn(|_| {
+            let octave = fbm.gen_octave::<StandardOctave>();
+            octave.post_construction(&mut fbm);
+            octave
+        });
+        let octaves = octaves.map(|octave| {
+            let (stored, period) = octave.finalize(&fbm);
+            (PerlinNoise::new(args.branch().with_period(period)), stored)
+        });
+
+        Self {
+            warp,
+            warp_strength: warp_strength * period.0,
+            octaves,
+        }
+    }
+}
+
+impl<const OCTAVES: usize> NoiseOp<Vec2> for WarpedRidgedTerrain<OCTAVES> {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let warp = Vec2::new(
+            self.warp[0].get(input).adapt::<f32>(),
+            self.warp[1].get(input).adapt::<f32>(),
+        );
+        let input = input + warp * self.warp_strength;
+
+        let mut acc = OctaveSumAccumulator(0.0);
+        for (noise, octave) in &self.octaves {
+            let ridge = 1.0 - noise.get(input).adapt::<f32>().abs();
+            acc.accumulate(ridge * ridge, octave);
+        }
+        UNorm::new_clamped(acc.finish())
+    }
+}
+
+/// Perlin fbm where each octave is domain warped by the sum of the octaves before it.
+/// Since the warp comes from the lower frequencies of the same noise, the distortion follows its
+/// features, which is more coherent than warping each octave independently. The result is summed
+/// like [`OctaveSum`](super::fbm::OctaveSum).
+///
+/// The sum of the octaves warps along x. Along y, each octave also samples a second, unrelated
+/// perlin noise at the same warped input, and the sum of those warps along y.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackWarpFbm<const OCTAVES: usize = 6> {
+    warp_strength: f32,
+    octaves: [(PerlinNoise, PerlinNoise, WeightedOctave); OCTAVES],
+}
+
+impl<const OCTAVES: usize> FeedbackWarpFbm<OCTAVES> {
+    /// Constructs a new [`FeedbackWarpFbm`] from its settings.
+    /// `warp_strength` is how far the input can be warped, relative to the period.
+    pub fn new(mut args: SpatialNoiseSettings, warp_strength: f32) -> Self {
+        let period = args.period;
+        let mut fbm = StandardFbm::new(period, 0.5, 0.5);
+        let octaves: [StandardOctave; OCTAVES] = core::array::from_fn(|_| {
+            let octave = fbm.gen_octave::<StandardOctave>();
+            octave.post_construction(&mut fbm);
+            octave
+        });
+        let octaves = octaves.map(|octave| {
+            let (stored, period) = octave.finalize(&fbm);
+            (
+                PerlinNoise::new(args.branch().with_period(period)),
+                stored,
+                period,
+            )
+        });
+        // the y noises branch after the x noises, so the x noises match standard fbm.
+        let octaves = octaves.map(|(x, stored, period)| {
+            (
+                x,
+                PerlinNoise::new(args.branch().with_period(period)),
+                stored,
+            )
+        });
+
+        Self {
+            warp_strength: warp_strength * period.0,
+            octaves,
+        }
+    }
+}
+
+impl<const OCTAVES: usize> NoiseOp<Vec2> for FeedbackWarpFbm<OCTAVES> {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let mut acc = OctaveSumAccumulator(0.0);
+        let mut acc_y = OctaveSumAccumulator(0.0);
+        for (noise, noise_y, octave) in &self.octaves {
+            let warped = input + Vec2::new(acc.0, acc_y.0) * self.warp_strength;
+            acc.accumulate(noise.get(warped), octave);
+            acc_y.accumulate(noise_y.get(warped), octave);
+        }
+        acc.finish()
+    }
+}
+
+/// Perlin fbm that tiles seamlessly every `world_size`.
+/// The first octave has `base_tiles` cells across the world, and each octave after it has
+/// `lacunarity` times as many. Each octave wraps its cells at its own tile count, so all of them
+/// repeat at the same world size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileableFbm<const OCTAVES: usize = 6> {
+    world_size: Vec2,
+    octaves: [(UVec2, Seeding, WeightedOctave); OCTAVES],
+}
+
+impl<const OCTAVES: usize> TileableFbm<OCTAVES> {
+    /// Constructs a new [`TileableFbm`] from its settings. The period of `args` is not used, since
+    /// that is determined by `world_size` and the tile counts.
+    pub fn new(
+        mut args: SpatialNoiseSettings,
+        world_size: Vec2,
+        base_tiles: UVec2,
+        lacunarity: u32,
+    ) -> Self {
+        let base_tiles = base_tiles.max(UVec2::ONE);
+        let lacunarity = lacunarity.max(1);
+
+        let mut fbm = StandardFbm::new(Period(1.0), 1.0 / lacunarity as f32, 0.5);
+        let octaves: [StandardOctave; OCTAVES] = core::array::from_fn(|_| {
+            let octave = fbm.gen_octave::<StandardOctave>();
+            octave.post_construction(&mut fbm);
+            octave
+        });
+        let mut tiles = base_tiles;
+        let octaves = octaves.map(|octave| {
+            let (stored, _period) = octave.finalize(&fbm);
+            let result = (tiles, args.seeding(), stored);
+            tiles = tiles.saturating_mul(UVec2::splat(lacunarity));
+            result
+        });
+
+        Self {
+            world_size,
+            octaves,
+        }
+    }
+}
+
+impl<const OCTAVES: usize> NoiseOp<Vec2> for TileableFbm<OCTAVES> {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let unit = input / self.world_size;
+        let grid = GridNoise::new_frequency(1.0);
+        let mut acc = OctaveSumAccumulator(0.0);
+        for (tiles, seeding, octave) in &self.octaves {
+            let point = grid.get(unit * tiles.as_vec2());
+            acc.accumulate(perlin_at(*seeding, point, |base| base % *tiles), octave);
+        }
+        UNorm::new_clamped(acc.finish() * 0.5 + 0.5)
+    }
+}
+
+/// Marble-like veining: a sine wave along the x axis whose phase is offset by turbulence.
+/// `noise` is usually turbulence, like fbm accumulated with
+/// [`TurbulenceOctaveSum`](super::fbm::TurbulenceOctaveSum), which bends the stripes into veins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marble<N> {
+    /// How many stripes there are per unit along the x axis.
+    pub frequency: f32,
+    /// How far, in units of x, the turbulence can shift the stripes.
+    pub turbulence_strength: f32,
+    /// The turbulence that bends the stripes.
+    pub noise: N,
+}
+
+/// easily implements [`Marble`] for different vectors
+macro_rules! impl_marble {
+    ($vec:ty) => {
+        impl<N: NoiseOp<$vec>> NoiseOp<$vec> for Marble<N>
+        where
+            N::Output: NoiseConverter<f32, Input = N::Output>,
+        {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: $vec) -> Self::Output {
+                let turbulence = self.noise.get(input).adapt::<f32>();
+                let phase = (input.x + turbulence * self.turbulence_strength) * self.frequency;
+                UNorm::new_clamped((phase * core::f32::consts::TAU).sin() * 0.5 + 0.5)
+            }
+        }
+    };
+}
+
+impl_marble!(Vec2);
+impl_marble!(Vec3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as noiz,
+        noise::{
+            fbm::OctaveSum,
+            noise_op,
+        },
+    };
+
+    #[test]
+    fn warped_ridged_terrain_is_unorm() {
+        let terrain = WarpedRidgedTerrain::<6>::new(SpatialNoiseSettings::new(31, 64.0), 0.5);
+        for y in -30..30 {
+            for x in -30..30 {
+                let value = terrain
+                    .get(Vec2::new(x as f32, y as f32) * 7.3)
+                    .adapt::<f32>();
+                assert!(value.is_finite());
+                assert!((0.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    noise_op! {
+        struct StandardPerlinFbm for Vec2 -> f32 = SpatialNoiseSettings
+        impl
+        loop OctaveSum where fbm = StandardFbm::new(args.period, 0.5, 0.5) enum [
+            4 where octave: WeightedOctave as fbm.gen_octave::<StandardOctave>() impl {
+                || *input;
+                fn PerlinNoise = args.branch().with_period(octave).into();
+            },
+        ];
+    }
+
+    #[test]
+    fn feedback_warp_without_strength_is_fbm() {
+        let settings = || SpatialNoiseSettings::new(12, 32.0);
+        let unwarped = FeedbackWarpFbm::<4>::new(settings(), 0.0);
+        let warped = FeedbackWarpFbm::<4>::new(settings(), 0.5);
+        let standard = StandardPerlinFbm::from(settings());
+        let mut difference = 0.0;
+        for y in -10..10 {
+            for x in -10..10 {
+                let input = Vec2::new(x as f32, y as f32) * 5.3;
+                let expected = standard.get(input);
+                assert!((unwarped.get(input) - expected).abs() < 1e-5);
+                difference += (warped.get(input) - expected).abs();
+            }
+        }
+        assert!(difference > 1.0);
+    }
+
+    #[test]
+    fn feedback_warp_uses_both_axes() {
+        let warped = FeedbackWarpFbm::<4>::new(SpatialNoiseSettings::new(12, 32.0), 0.5);
+        // warping only along the diagonal should look different.
+        let mut diagonal = warped.clone();
+        for (x, y, _) in &mut diagonal.octaves {
+            *y = x.clone();
+        }
+        let (noise_x, noise_y, first) = &warped.octaves[0];
+        let mut output_difference = 0.0;
+        let mut warp_x = 0.0;
+        let mut warp_y = 0.0;
+        let mut off_diagonal = 0.0;
+        for y in -10..10 {
+            for x in -10..10 {
+                let input = Vec2::new(x as f32, y as f32) * 5.3;
+                output_difference += (diagonal.get(input) - warped.get(input)).abs();
+
+                // the warp of the second octave, from the first.
+                let mut acc = OctaveSumAccumulator(0.0);
+                let mut acc_y = OctaveSumAccumulator(0.0);
+                acc.accumulate(noise_x.get(input), first);
+                acc_y.accumulate(noise_y.get(input), first);
+                warp_x += acc.0.abs();
+                warp_y += acc_y.0.abs();
+                off_diagonal += (acc.0 - acc_y.0).abs();
+            }
+        }
+        assert!(output_difference > 1.0);
+        assert!(warp_x > 1.0);
+        assert!(warp_y > 1.0);
+        assert!(off_diagonal > 1.0);
+    }
+
+    #[test]
+    fn fast_worley_matches_exact_in_cell_interiors() {
+        let fast = FastWorley::new(SpatialNoiseSettings::new(5, 4.0));
+        let seed = SpatialNoiseSettings::new(5, 4.0).rand_32();
+        // the approximation halves the nudge range.
+        let exact = Voronoi::<2, Worley<EuclideanDistance, Nearest>>::new_default(0.5, seed);
+        let mut worst = 0f32;
+        for y in 0..40 {
+            for x in 0..40 {
+                let input = Vec2::new(x as f32, y as f32) * 0.37;
+                let point = fast.grid.get(input);
+                let fast = fast.get(input).adapt::<f32>();
+                let exact = exact.get(point.clone()).adapt::<f32>();
+                let interior = (point.offset - Vec2::splat(0.5)).abs().max_element() < 0.25;
+                if interior {
+                    worst = worst.max((fast - exact).abs());
+                }
+            }
+        }
+        assert!(worst < 1e-5);
+    }
+
+    #[test]
+    fn tileable_fbm_edges_match() {
+        let world = Vec2::new(96.0, 64.0);
+        let fbm = TileableFbm::<4>::new(
+            SpatialNoiseSettings::new(8, 1.0),
+            world,
+            UVec2::new(3, 2),
+            2,
+        );
+        for i in 0..=40 {
+            let t = i as f32 / 40.0;
+            let left = fbm.get(Vec2::new(0.0, t * world.y)).adapt::<f32>();
+            let right = fbm.get(Vec2::new(world.x, t * world.y)).adapt::<f32>();
+            assert!((left - right).abs() < 1e-5);
+            let bottom = fbm.get(Vec2::new(t * world.x, 0.0)).adapt::<f32>();
+            let top = fbm.get(Vec2::new(t * world.x, world.y)).adapt::<f32>();
+            assert!((bottom - top).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn marble_without_turbulence_is_stripes() {
+        let marble = Marble {
+            frequency: 0.25,
+            turbulence_strength: 0.0,
+            noise: PerlinNoise::new(SpatialNoiseSettings::new(3, 8.0)),
+        };
+        for i in 0..40 {
+            let x = i as f32 * 0.31;
+            let expected = (x * 0.25 * core::f32::consts::TAU).sin() * 0.5 + 0.5;
+            for y in [-7.0, 0.0, 2.5] {
+                let value = marble.get(Vec2::new(x, y)).adapt::<f32>();
+                assert!((value - expected).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn seamless_worley_faces_match() {
+        let tiles = UVec3::new(3, 2, 4);
+        let worley = SeamlessWorley3d::new(SpatialNoiseSettings::new(8, 2.0), tiles);
+        let size = tiles.as_vec3() * 2.0;
+        for i in 0..=8 {
+            for j in 0..=8 {
+                let (u, v) = (i as f32 / 8.0, j as f32 / 8.0);
+                for (a, b) in [
+                    (Vec3::new(0.0, u, v), Vec3::new(1.0, u, v)),
+                    (Vec3::new(u, 0.0, v), Vec3::new(u, 1.0, v)),
+                    (Vec3::new(u, v, 0.0), Vec3::new(u, v, 1.0)),
+                ] {
+                    let a = worley.get(a * size).adapt::<f32>();
+                    let b = worley.get(b * size).adapt::<f32>();
+                    assert!((a - b).abs() < 1e-5);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! This module allows measuring how expensive noise is to sample.
+
+use core::{
+    sync::atomic::{
+        AtomicU64,
+        Ordering,
+    },
+    time::Duration,
+};
+
+use super::NoiseOp;
+
+/// The statistics collected by a [`Profiled`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProfileStats {
+    /// The number of times the noise was sampled.
+    pub calls: u64,
+    /// The total time spent sampling the noise.
+    pub total: Duration,
+}
+
+impl ProfileStats {
+    /// The average time spent per sample, or zero if there were none.
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total.as_nanos() / self.calls as u128) as u64)
+        }
+    }
+}
+
+/// A [`NoiseOp`] that counts how many times `N` is sampled and how long it takes. This helps find
+/// the expensive parts of a pipeline.
+///
+/// Stats are only collected in debug builds. In release builds, this just samples `N`, and
+/// [`stats`](Self::stats) is always empty.
+#[derive(Debug, Default)]
+pub struct Profiled<N> {
+    /// The noise being profiled.
+    pub noise: N,
+    calls: AtomicU64,
+    nanos: AtomicU64,
+}
+
+impl<N> Profiled<N> {
+    /// Starts profiling this `noise`.
+    pub fn new(noise: N) -> Self {
+        Self {
+            noise,
+            calls: AtomicU64::new(0),
+            nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Gets the stats collected so far.
+    pub fn stats(&self) -> ProfileStats {
+        ProfileStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Clears the stats collected so far.
+    pub fn reset(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+        self.nanos.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<I, N: NoiseOp<I>> NoiseOp<I> for Profiled<N> {
+    type Output = N::Output;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        #[cfg(debug_assertions)]
+        {
+            let start = std::time::Instant::now();
+            let result = self.noise.get(input);
+            let nanos = start.elapsed().as_nanos() as u64;
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.nanos.fetch_add(nanos, Ordering::Relaxed);
+            result
+        }
+        #[cfg(not(debug_assertions))]
+        self.noise.get(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::*;
+    use crate::noise::grid::GridNoise;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn profiled_counts_calls() {
+        let profiled = Profiled::new(GridNoise::new_frequency(1.0));
+        for i in 0..25 {
+            let _point = profiled.get(Vec2::splat(i as f32));
+        }
+        assert_eq!(profiled.stats().calls, 25);
+        profiled.reset();
+        assert_eq!(profiled.stats(), ProfileStats::default());
+    }
+
+    #[test]
+    fn average_handles_many_calls() {
+        let stats = ProfileStats {
+            calls: 1 << 32,
+            total: Duration::from_secs(3 << 32),
+        };
+        assert_eq!(stats.average(), Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,95 @@
+//! This module contains helpers that render [`Noise`] into image buffers.
+
+use bevy_math::Vec2;
+
+use super::{
+    Noise,
+    NoiseType,
+    conversions::NoiseConverter,
+    norm::UNorm,
+};
+
+/// Samples `noise` on a `width` by `height` image and packs each sample into a grayscale byte, row
+/// by row. The top left pixel is sampled at `origin`, and each pixel is `scale` further along the
+/// x or y axis than the last.
+pub fn render_to_luma8<N: Noise>(
+    noise: &N,
+    width: usize,
+    height: usize,
+    scale: f32,
+    origin: Vec2,
+) -> Vec<u8>
+where
+    N::Output: NoiseConverter<UNorm, Input = N::Output>,
+    Vec2: NoiseConverter<N::Input, Input = Vec2>,
+{
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let value = noise.sample(origin + Vec2::new(x as f32, y as f32) * scale);
+            pixels.push(value.adapt::<UNorm>().fill_u8());
+        }
+    }
+    pixels
+}
+
+/// The same as [`render_to_luma8`], but each sample is packed into 4 bytes of RGBA, with the
+/// grayscale value in each color channel and a fully opaque alpha.
+pub fn render_to_rgba8<N: Noise>(
+    noise: &N,
+    width: usize,
+    height: usize,
+    scale: f32,
+    origin: Vec2,
+) -> Vec<u8>
+where
+    N::Output: NoiseConverter<UNorm, Input = N::Output>,
+    Vec2: NoiseConverter<N::Input, Input = Vec2>,
+{
+    render_to_luma8(noise, width, height, scale, origin)
+        .into_iter()
+        .flat_map(|luma| [luma, luma, luma, u8::MAX])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::NoiseOp;
+
+    /// A diagonal ramp that is 0.0 at the origin and rises by 1/8 per unit along each axis.
+    struct Diagonal;
+
+    impl NoiseOp<Vec2> for Diagonal {
+        type Output = UNorm;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            UNorm::new_clamped(input.element_sum() / 8.0)
+        }
+    }
+
+    impl Noise for Diagonal {
+        type Input = Vec2;
+    }
+
+    #[test]
+    fn renders_row_by_row() {
+        let luma = render_to_luma8(&Diagonal, 4, 4, 1.0, Vec2::ZERO);
+        #[rustfmt::skip]
+        assert_eq!(luma, [
+            0, 32, 64, 96,
+            32, 64, 96, 128,
+            64, 96, 128, 160,
+            96, 128, 160, 192,
+        ]);
+
+        let shifted = render_to_luma8(&Diagonal, 4, 4, 0.5, Vec2::new(1.0, 0.0));
+        assert_eq!(shifted[..4], [32, 48, 64, 80]);
+        assert_eq!(shifted[4], 48);
+
+        let rgba = render_to_rgba8(&Diagonal, 4, 4, 1.0, Vec2::ZERO);
+        assert_eq!(rgba.len(), 64);
+        assert_eq!(rgba[4..8], [32, 32, 32, 255]);
+        assert_eq!(rgba[60..], [192, 192, 192, 255]);
+    }
+}
@@ -1,6 +1,10 @@
 //! Allows noise types to be given a seed.
 
+use core::f32::consts::TAU;
+use std::marker::PhantomData;
+
 use bevy_math::{
+    Quat,
     U8Vec2,
     U8Vec3,
     U8Vec4,
@@ -19,6 +23,11 @@ use super::{
     NoiseOp,
     NoiseType,
     associating::Associated,
+    grid::{
+        GridPoint2,
+        GridPoint3,
+    },
+    norm::UNorm,
     white::{
         White8,
         White16,
@@ -27,6 +36,7 @@ use super::{
         White128,
     },
 };
+use crate::spatial::named_array::NamedArrayIndices;
 
 /// Marks the type as being able to be given aseed. For example, grid points implement this so that
 /// each cell in a grid can have a unique seed.
@@ -73,6 +83,164 @@ impl<T> NoiseOp<Seeded<T>> for SeedOf {
     }
 }
 
+/// A [`NoiseOp`] that folds a channel id into the seed of a [`Seeded`] value via [`White32`].
+/// This gives each channel, like the components of a color, its own stream of values at the same
+/// position, without offsetting seeds by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChannelHash {
+    /// The channel to fold into the seed.
+    pub channel: u32,
+}
+
+impl<T: NoiseType> NoiseOp<Seeded<T>> for ChannelHash {
+    type Output = Seeded<T>;
+
+    #[inline]
+    fn get(&self, input: Seeded<T>) -> Self::Output {
+        Seeded {
+            meta: Seed(White32(input.meta.0).get([self.channel])),
+            value: input.value,
+        }
+    }
+}
+
+/// A [`NoiseOp`] that samples `N` on `K` channels of a [`Seeded`] value, folding each channel into
+/// the seed with [`ChannelHash`]. This packs several decorrelated layers, like height, temperature,
+/// and moisture, into one sample, sharing the work done before seeding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MultiChannel<N, const K: usize>(pub N);
+
+impl<T: NoiseType + Clone, N: NoiseOp<Seeded<T>>, const K: usize> NoiseOp<Seeded<T>>
+    for MultiChannel<N, K>
+{
+    type Output = [N::Output; K];
+
+    #[inline]
+    fn get(&self, input: Seeded<T>) -> Self::Output {
+        core::array::from_fn(|channel| {
+            let channel = ChannelHash {
+                channel: channel as u32,
+            };
+            self.0.get(channel.get(input.clone()))
+        })
+    }
+}
+
+/// A [`NoiseOp`] that gives each [`Seeded`] value a normally distributed random number, with this
+/// [`mean`](Self::mean) and [`std_dev`](Self::std_dev). Many natural properties, like heights or
+/// sizes, look more natural this way than when uniformly distributed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellGaussian {
+    /// The average of the values.
+    pub mean: f32,
+    /// The standard deviation of the values.
+    pub std_dev: f32,
+}
+
+impl<T> NoiseOp<Seeded<T>> for CellGaussian {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Seeded<T>) -> Self::Output {
+        // Box–Muller transform. The first value can't be 0, since its log is taken.
+        // Neighboring rng inputs are correlated, so the second value is hashed from the first.
+        let first = input.rng(0);
+        let u1 = first.adapt::<UNorm>().adapt::<f32>().max(f32::MIN_POSITIVE);
+        let u2 = White32(first).get(1).adapt::<UNorm>().adapt::<f32>();
+        let normal = (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos();
+        self.mean + normal * self.std_dev
+    }
+}
+
+/// A [`NoiseOp`] that gives each cell a random rotation, uniformly distributed over all rotations.
+/// This is useful for randomly orienting objects scattered through 3d cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CellQuaternion;
+
+impl NoiseOp<Seeded<GridPoint3>> for CellQuaternion {
+    type Output = Quat;
+
+    #[inline]
+    fn get(&self, input: Seeded<GridPoint3>) -> Self::Output {
+        // Shoemake's method: three uniform values map to a uniform unit quaternion.
+        // Neighboring rng inputs are correlated, so each value is hashed from the last.
+        let first = input.rng(0);
+        let second = White32(first).get(1);
+        let third = White32(second).get(2);
+        let [u1, u2, u3] = [first, second, third].map(|x| x.adapt::<UNorm>().adapt::<f32>());
+        let low = (1.0 - u1).sqrt();
+        let high = u1.sqrt();
+        let (sin2, cos2) = (TAU * u2).sin_cos();
+        let (sin3, cos3) = (TAU * u3).sin_cos();
+        Quat::from_xyzw(low * sin2, low * cos2, high * sin3, high * cos3)
+    }
+}
+
+/// A [`NoiseOp`] that picks one of the names of a [`name_array!`](crate::name_array) enum for each
+/// cell based on its seed. This is useful for assigning one of a small fixed set of tile types to
+/// each cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CellTag<T>(pub PhantomData<T>);
+
+impl<T> CellTag<T> {
+    /// Constructs a new [`CellTag`]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: NamedArrayIndices + NoiseType + Copy, G> NoiseOp<Seeded<G>> for CellTag<T> {
+    type Output = T;
+
+    #[inline]
+    fn get(&self, input: Seeded<G>) -> Self::Output {
+        T::INDEX_TO_NAME[input.seed() as usize % T::LEN]
+    }
+}
+
+/// A [`NoiseOp`] that decides whether each cell has a feature, like a large tree, such that no two
+/// adjacent cells (including diagonally) both have one.
+/// Each cell gets a random value, and only cells whose value is below
+/// [`probability`](Self::probability) are candidates. A candidate has the feature only if its value
+/// is greater than all of its neighboring candidates'.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpacedFeature {
+    /// The chance that a cell is a candidate for the feature. Because neighboring candidates thin
+    /// each other out, fewer cells will actually have the feature.
+    pub probability: f32,
+    /// The seed of the cells' random values.
+    pub seed: u32,
+}
+
+impl SpacedFeature {
+    /// Gets the random value of the cell at `base`.
+    #[inline]
+    fn value_of(&self, base: UVec2) -> f32 {
+        Seeding(self.seed)
+            .get(base)
+            .rng(0)
+            .adapt::<UNorm>()
+            .adapt::<f32>()
+    }
+}
+
+impl NoiseOp<GridPoint2> for SpacedFeature {
+    type Output = bool;
+
+    #[inline]
+    fn get(&self, input: GridPoint2) -> Self::Output {
+        let value = self.value_of(input.base);
+        value < self.probability
+            && input.surroundings().0.into_iter().all(|neighbor| {
+                if neighbor.base == input.base {
+                    return true;
+                }
+                let other = self.value_of(neighbor.base);
+                other >= self.probability || other < value
+            })
+    }
+}
+
 impl<T> Seeded<T> {
     /// Gets the seed of this [`Seeded`].
     #[inline]
@@ -115,3 +283,151 @@ impl_seedable!(UVec4, White32, u32);
 impl_seedable!(U64Vec2, White64, u64);
 impl_seedable!(U64Vec3, White64, u64);
 impl_seedable!(U64Vec4, White64, u64);
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{
+        Vec2,
+        Vec3,
+    };
+
+    use super::*;
+
+    #[test]
+    fn seeded_maps_keep_seed() {
+        let seeded = Seeding(9).get(UVec2::new(5, 8));
+        let doubled = seeded.map_ref(|point| *point * 2);
+        assert_eq!(doubled.value, UVec2::new(10, 16));
+        assert_eq!(doubled.seed(), seeded.seed());
+        let summed = seeded.map(|point| point.element_sum());
+        assert_eq!(summed.value, 13);
+        assert_eq!(summed.rng(2), seeded.rng(2));
+    }
+
+    #[test]
+    fn channels_are_distinct() {
+        let seeded = Seeding(4).get(UVec2::new(17, 3));
+        let values = [0, 1, 2, 3].map(|channel| {
+            ChannelHash { channel }
+                .get(seeded)
+                .rng(0)
+                .adapt::<UNorm>()
+                .adapt::<f32>()
+        });
+        for (index, value) in values.iter().enumerate() {
+            for other in &values[index + 1..] {
+                assert_ne!(value, other);
+            }
+        }
+    }
+
+    #[test]
+    fn multi_channel_matches_channels() {
+        let seeded = Seeding(4).get(UVec2::new(17, 3));
+        let channels = MultiChannel::<_, 4>(SeedOf).get(seeded);
+        for (channel, seed) in channels.iter().enumerate() {
+            let expected = ChannelHash {
+                channel: channel as u32,
+            }
+            .get(seeded)
+            .seed();
+            assert_eq!(*seed, expected);
+            for other in &channels[channel + 1..] {
+                assert_ne!(seed, other);
+            }
+        }
+    }
+
+    #[test]
+    fn cell_gaussian_matches_parameters() {
+        let gaussian = CellGaussian {
+            mean: 5.0,
+            std_dev: 2.0,
+        };
+        let seeding = Seeding(21);
+        let samples = (0..128 * 128)
+            .map(|i| gaussian.get(seeding.get(UVec2::new(i % 128, i / 128))))
+            .collect::<Vec<_>>();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let variance =
+            samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / samples.len() as f32;
+        assert!((mean - 5.0).abs() < 0.1);
+        assert!((variance - 4.0).abs() < 0.3);
+        assert!(samples.iter().all(|x| x.is_finite()));
+    }
+
+    crate::name_array! {
+        pub struct Tiles,
+        pub enum Tile: u8 {
+            Grass,
+            Water,
+            Sand,
+            Rock,
+        }
+    }
+
+    impl NoiseType for Tile {}
+
+    #[test]
+    fn cell_tags_cover_all_names() {
+        let tags = CellTag::<Tile>::new();
+        let seeding = Seeding(4);
+        let mut seen = Tiles([false; 4]);
+        for i in 0..256 {
+            let cell = UVec2::new(i % 16, i / 16);
+            let tag = tags.get(seeding.get(cell));
+            assert_eq!(tag, tags.get(seeding.get(cell)));
+            seen[tag] = true;
+        }
+        assert!(seen.into_iter().all(|seen| seen));
+    }
+
+    #[test]
+    fn spaced_features_are_not_adjacent() {
+        let features = SpacedFeature {
+            probability: 0.6,
+            seed: 13,
+        };
+        let present = |x: u32, y: u32| {
+            features.get(GridPoint2 {
+                base: UVec2::new(x, y),
+                offset: Vec2::splat(0.5),
+            })
+        };
+        let mut count = 0;
+        for y in 1..63 {
+            for x in 1..63 {
+                if !present(x, y) {
+                    continue;
+                }
+                count += 1;
+                for (dx, dy) in [(1, 0), (1, 1), (0, 1), (-1, 1)] {
+                    assert!(!present(
+                        x.wrapping_add_signed(dx),
+                        y.wrapping_add_signed(dy)
+                    ));
+                }
+            }
+        }
+        assert!(count > 100);
+    }
+
+    #[test]
+    fn cell_quaternions_are_reproducible_rotations() {
+        let seeding = Seeding(3);
+        let cell = |base: UVec3, offset: Vec3| seeding.get(GridPoint3 { base, offset });
+        let quat = CellQuaternion.get(cell(UVec3::new(7, 1, 9), Vec3::ZERO));
+        assert_eq!(
+            quat,
+            CellQuaternion.get(cell(UVec3::new(7, 1, 9), Vec3::splat(0.5)))
+        );
+        assert_ne!(
+            quat,
+            CellQuaternion.get(cell(UVec3::new(8, 1, 9), Vec3::ZERO))
+        );
+        for x in 0..32 {
+            let quat = CellQuaternion.get(cell(UVec3::new(x, x * 3, 5), Vec3::ZERO));
+            assert!(quat.is_normalized());
+        }
+    }
+}
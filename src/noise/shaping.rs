@@ -0,0 +1,474 @@
+//! This module contains noise operations that reshape scalar and vector noise values.
+
+use bevy_math::{
+    Curve,
+    UVec2,
+    Vec2,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    norm::{
+        SNorm,
+        UNorm,
+        make_nonzero_f32,
+    },
+};
+
+/// A [`NoiseOp`] that passes the input through a sine wave, producing ripples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SineWave {
+    /// How many radians the wave advances per unit of input.
+    pub frequency: f32,
+    /// The offset of the wave in radians.
+    pub phase: f32,
+}
+
+/// A [`NoiseOp`] that passes the input through a cosine wave, producing ripples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CosineWave {
+    /// How many radians the wave advances per unit of input.
+    pub frequency: f32,
+    /// The offset of the wave in radians.
+    pub phase: f32,
+}
+
+impl NoiseOp<f32> for SineWave {
+    type Output = SNorm;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        SNorm::new_clamped((input * self.frequency + self.phase).sin())
+    }
+}
+
+impl NoiseOp<f32> for CosineWave {
+    type Output = SNorm;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        SNorm::new_clamped((input * self.frequency + self.phase).cos())
+    }
+}
+
+/// The shape of a [`Waveform`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WaveKind {
+    /// Rises linearly from 0 to 1 over each period, then drops back to 0.
+    #[default]
+    Sawtooth,
+    /// Rises linearly from 0 to 1 over the first half of each period and falls back over the
+    /// second.
+    Triangle,
+    /// Is 1 for the first half of each period and 0 for the second.
+    Square,
+}
+
+/// A [`NoiseOp`] that turns a scalar into a repeating waveform, which is useful for stripes and
+/// ridges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Waveform {
+    /// The shape of the wave.
+    pub kind: WaveKind,
+    /// How many periods the wave completes per unit of input.
+    pub frequency: f32,
+}
+
+impl NoiseOp<f32> for Waveform {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        let sawtooth = UNorm::new_rolling(input * self.frequency);
+        match self.kind {
+            WaveKind::Sawtooth => sawtooth,
+            WaveKind::Triangle => {
+                UNorm::new_clamped(1.0 - (1.0 - 2.0 * sawtooth.adapt::<f32>()).abs())
+            }
+            WaveKind::Square => UNorm::new_clamped(if sawtooth.adapt::<f32>() < 0.5 {
+                1.0
+            } else {
+                0.0
+            }),
+        }
+    }
+}
+
+/// A [`NoiseOp`] that maps a value through a lookup table, linearly interpolating between its
+/// entries. The first entry is for 0.0, the last is for 1.0, and the rest are spaced evenly
+/// between. This is a fast way to apply an arbitrary curve, like one painted by an artist.
+/// An empty table leaves the value unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lut<'a> {
+    /// The precomputed outputs.
+    pub table: &'a [f32],
+}
+
+impl NoiseOp<UNorm> for Lut<'_> {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: UNorm) -> Self::Output {
+        let value = input.adapt::<f32>();
+        let Some(last) = self.table.len().checked_sub(1) else {
+            return value;
+        };
+        let position = value * last as f32;
+        let index = (position as usize).min(last);
+        let next = (index + 1).min(last);
+        let t = position - index as f32;
+        self.table[index] + (self.table[next] - self.table[index]) * t
+    }
+}
+
+/// A [`NoiseOp`] that linearly remaps a value from one range to another, clamping it to the new
+/// range. Either range may be inverted, so remapping `(0.0, 1.0)` to `(1.0, 0.0)` flips the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Remap {
+    /// The range of the input, where the first value maps to the start of [`to`](Self::to).
+    pub from: (f32, f32),
+    /// The range of the output.
+    pub to: (f32, f32),
+}
+
+impl Default for Remap {
+    fn default() -> Self {
+        Self {
+            from: (0.0, 1.0),
+            to: (0.0, 1.0),
+        }
+    }
+}
+
+impl NoiseOp<f32> for Remap {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        let t = (input - self.from.0) / make_nonzero_f32(self.from.1 - self.from.0);
+        self.to.0 + (self.to.1 - self.to.0) * t.clamp(0.0, 1.0)
+    }
+}
+
+/// A [`NoiseOp`] that passes a value through a [`Curve`], clamping it to the curve's domain.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RemapCurve<C>(pub C);
+
+impl<C: Curve<f32>> NoiseOp<f32> for RemapCurve<C> {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        self.0.sample_clamped(input)
+    }
+}
+
+/// A [`NoiseOp`] that takes the magnitude of a signed value, folding it into an unsigned one.
+/// Applying this to each octave of signed noise produces classic turbulence.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Abs;
+
+impl NoiseOp<SNorm> for Abs {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: SNorm) -> Self::Output {
+        input.split_to_unorm()
+    }
+}
+
+impl NoiseOp<f32> for Abs {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: f32) -> Self::Output {
+        input.abs()
+    }
+}
+
+/// A [`NoiseOp`] that raises a value in 0..=1 to a power. Exponents above 1.0 push values towards
+/// 0.0, like sharpening the peaks of a heightmap, and exponents below 1.0 push them towards 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pow {
+    /// The power to raise the value to.
+    pub exponent: f32,
+}
+
+impl Pow {
+    #[inline]
+    fn shape(&self, t: f32) -> f32 {
+        t.powf(self.exponent)
+    }
+}
+
+/// A [`NoiseOp`] that biases a value in 0..=1 towards 0.0 or 1.0 with Perlin's bias curve, which
+/// maps 0.5 to [`amount`](Self::amount). An amount of 0.5 leaves the value unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bias {
+    /// Where 0.5 ends up. This should be between 0.0 and 1.0, exclusive.
+    pub amount: f32,
+}
+
+impl Bias {
+    #[inline]
+    fn shape(&self, t: f32) -> f32 {
+        t.powf(self.amount.ln() / 0.5f32.ln())
+    }
+}
+
+/// A [`NoiseOp`] that adjusts the contrast of a value in 0..=1 with Perlin's gain curve.
+/// Amounts above 0.5 push values towards the ends, and amounts below 0.5 push them towards the
+/// middle. An amount of 0.5 leaves the value unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gain {
+    /// How much contrast to add. This should be between 0.0 and 1.0, exclusive.
+    pub amount: f32,
+}
+
+impl Gain {
+    #[inline]
+    fn shape(&self, t: f32) -> f32 {
+        let bias = Bias {
+            amount: 1.0 - self.amount,
+        };
+        if t < 0.5 {
+            bias.shape(2.0 * t) * 0.5
+        } else {
+            1.0 - bias.shape(2.0 - 2.0 * t) * 0.5
+        }
+    }
+}
+
+/// easily implements scalar shaping ops for [`UNorm`] and `f32`
+macro_rules! impl_scalar_shaping {
+    ($t:ty) => {
+        impl NoiseOp<UNorm> for $t {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: UNorm) -> Self::Output {
+                UNorm::new_clamped(self.shape(input.adapt::<f32>()))
+            }
+        }
+
+        impl NoiseOp<f32> for $t {
+            type Output = f32;
+
+            #[inline]
+            fn get(&self, input: f32) -> Self::Output {
+                self.shape(input.clamp(0.0, 1.0)).clamp(0.0, 1.0)
+            }
+        }
+    };
+}
+
+impl_scalar_shaping!(Pow);
+impl_scalar_shaping!(Bias);
+impl_scalar_shaping!(Gain);
+
+/// A [`NoiseOp`] that snaps a vector to the nearest of `DIRS` evenly spaced unit directions,
+/// starting from the positive x axis. Zero vectors, which have no direction, become
+/// [`Vec2::ZERO`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QuantizeDirection<const DIRS: usize>;
+
+impl<const DIRS: usize> NoiseOp<Vec2> for QuantizeDirection<DIRS> {
+    type Output = Vec2;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        if DIRS == 0 || input == Vec2::ZERO || !input.is_finite() {
+            return Vec2::ZERO;
+        }
+        let step = core::f32::consts::TAU / DIRS as f32;
+        let snapped = (input.to_angle() / step).round() * step;
+        Vec2::from_angle(snapped)
+    }
+}
+
+/// A [`NoiseOp`] that quantizes a value to one of `levels` palette indices, using ordered (bayer)
+/// dithering based on the pixel coordinate to reduce banding.
+/// The input is the value and its pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaletteIndex {
+    /// The number of indices in the palette.
+    pub levels: u32,
+    /// How much dithering to apply, where 0.0 is none and 1.0 is full.
+    pub dither: f32,
+}
+
+impl PaletteIndex {
+    /// The 4x4 bayer matrix used for dithering.
+    const BAYER: [[u32; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+}
+
+impl NoiseOp<(UNorm, UVec2)> for PaletteIndex {
+    type Output = u32;
+
+    #[inline]
+    fn get(&self, input: (UNorm, UVec2)) -> Self::Output {
+        let (value, pixel) = input;
+        let max = self.levels.saturating_sub(1);
+        let threshold =
+            (Self::BAYER[(pixel.y % 4) as usize][(pixel.x % 4) as usize] as f32 + 0.5) / 16.0 - 0.5;
+        let index = value.adapt::<f32>() * max as f32 + 0.5 + threshold * self.dither;
+        (index.max(0.0) as u32).min(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::curve::{
+        FunctionCurve,
+        Interval,
+    };
+
+    use super::*;
+
+    #[test]
+    fn zero_frequency_waves_are_constant() {
+        let sine = SineWave {
+            frequency: 0.0,
+            phase: 0.7,
+        };
+        let cosine = CosineWave {
+            frequency: 0.0,
+            phase: 0.7,
+        };
+        for input in [-12.5, 0.0, 0.3, 8.0, 1000.0] {
+            assert_eq!(sine.get(input), sine.get(0.0));
+            assert_eq!(cosine.get(input), cosine.get(0.0));
+        }
+    }
+
+    #[test]
+    fn gain_and_bias_curves() {
+        let mut previous = [-1.0; 3];
+        for i in 0..=20 {
+            let t = i as f32 / 20.0;
+            assert!((Gain { amount: 0.5 }.get(t) - t).abs() < 1e-5);
+            assert!((Bias { amount: 0.5 }.get(t) - t).abs() < 1e-5);
+            assert!((Pow { exponent: 1.0 }.get(t) - t).abs() < 1e-6);
+
+            let shaped = [
+                Gain { amount: 0.8 }
+                    .get(UNorm::new_clamped(t))
+                    .adapt::<f32>(),
+                Bias { amount: 0.2 }
+                    .get(UNorm::new_clamped(t))
+                    .adapt::<f32>(),
+                Pow { exponent: 2.5 }
+                    .get(UNorm::new_clamped(t))
+                    .adapt::<f32>(),
+            ];
+            for (value, previous) in shaped.into_iter().zip(&mut previous) {
+                assert!((0.0..=1.0).contains(&value));
+                assert!(value >= *previous);
+                *previous = value;
+            }
+        }
+        assert!((Bias { amount: 0.2 }.get(0.5) - 0.2).abs() < 1e-5);
+        assert!(Gain { amount: 0.8 }.get(0.25) < 0.25);
+        assert!(Gain { amount: 0.2 }.get(0.25) > 0.25);
+    }
+
+    #[test]
+    fn waveforms_at_quarter_period() {
+        let wave = |kind| {
+            Waveform {
+                kind,
+                frequency: 0.5,
+            }
+            .get(0.5 + 2.0 * 7.0)
+            .adapt::<f32>()
+        };
+        assert!((wave(WaveKind::Sawtooth) - 0.25).abs() < 1e-5);
+        assert!((wave(WaveKind::Triangle) - 0.5).abs() < 1e-5);
+        assert!(wave(WaveKind::Square) > 0.99);
+    }
+
+    #[test]
+    fn linear_lut_is_identity() {
+        let table: [f32; 17] = core::array::from_fn(|i| i as f32 / 16.0);
+        let lut = Lut { table: &table };
+        for value in [0.0, 0.03, 0.25, 0.5, 0.61, 0.99, 1.0] {
+            let value = UNorm::new_clamped(value);
+            assert!((lut.get(value) - value.adapt::<f32>()).abs() < 1e-5);
+        }
+
+        let squares = [0.0, 0.25, 1.0];
+        let lut = Lut { table: &squares };
+        assert!((lut.get(UNorm::new_clamped(0.75)) - 0.625).abs() < 1e-5);
+        let empty = Lut { table: &[] };
+        assert_eq!(empty.get(UNorm::new_clamped(0.3)), 0.3);
+    }
+
+    #[test]
+    fn quantize_snaps_to_nearest_direction() {
+        let compass = QuantizeDirection::<4>;
+        let snapped = compass.get(Vec2::from_angle(10f32.to_radians()) * 3.0);
+        assert!(snapped.abs_diff_eq(Vec2::X, 1e-6));
+        let snapped = compass.get(Vec2::new(-0.1, -2.0));
+        assert!(snapped.abs_diff_eq(Vec2::NEG_Y, 1e-6));
+        assert_eq!(compass.get(Vec2::ZERO), Vec2::ZERO);
+    }
+
+    #[test]
+    fn palette_dithering_preserves_mean() {
+        let exact = PaletteIndex {
+            levels: 5,
+            dither: 0.0,
+        };
+        let dithered = PaletteIndex {
+            levels: 5,
+            dither: 1.0,
+        };
+        for value in [0.0, 0.1, 0.3, 0.55, 0.8, 1.0] {
+            let value = UNorm::new_clamped(value);
+            let expected = (value.adapt::<f32>() * 4.0).round() as u32;
+            let mut total = 0;
+            for y in 0..4 {
+                for x in 0..4 {
+                    assert_eq!(exact.get((value, UVec2::new(x, y))), expected);
+                    total += dithered.get((value, UVec2::new(x, y)));
+                }
+            }
+            let mean = total as f32 / 16.0;
+            assert!((mean - value.adapt::<f32>() * 4.0).abs() <= 1.0 / 16.0);
+        }
+    }
+
+    #[test]
+    fn remap_ranges() {
+        let identity = Remap::default();
+        for input in [0.0, 0.25, 0.5, 1.0] {
+            assert!((identity.get(input) - input).abs() < 1e-5);
+        }
+
+        let inverted = Remap {
+            from: (-1.0, 1.0),
+            to: (1.0, 0.0),
+        };
+        assert!((inverted.get(-1.0) - 1.0).abs() < 1e-5);
+        assert!((inverted.get(0.0) - 0.5).abs() < 1e-5);
+        assert!(inverted.get(1.0).abs() < 1e-5);
+        assert_eq!(inverted.get(5.0), 0.0);
+        assert_eq!(inverted.get(-5.0), 1.0);
+
+        let curve = RemapCurve(FunctionCurve::new(Interval::UNIT, |x: f32| x * x));
+        assert_eq!(curve.get(0.5), 0.25);
+        assert_eq!(curve.get(3.0), 1.0);
+        assert_eq!(curve.get(-3.0), 0.0);
+    }
+
+    #[test]
+    fn abs_takes_magnitude() {
+        let negative = SNorm::new_clamped(-0.75);
+        let positive = SNorm::new_clamped(0.75);
+        assert_eq!(Abs.get(negative).adapt::<f32>(), positive.adapt::<f32>());
+        assert_eq!(Abs.get(positive).adapt::<f32>(), positive.adapt::<f32>());
+        assert_eq!(Abs.get(-2.5f32), 2.5);
+        assert_eq!(Abs.get(2.5f32), 2.5);
+    }
+}
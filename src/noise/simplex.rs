@@ -0,0 +1,236 @@
+//! This module allows simplex noise, which uses a lattice of triangles (or tetrahedrons) instead of
+//! squares (or cubes). This has fewer directional artifacts than the orthogonal grid.
+
+use bevy_math::{
+    IVec2,
+    IVec3,
+    UVec2,
+    UVec3,
+    Vec2,
+    Vec3,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    Period,
+    conversions::NoiseConverter,
+    grid::{
+        GridPoint2,
+        GridPoint3,
+    },
+    norm::make_nonzero_f32,
+};
+
+/// A noise that converts a vector input to the corners of the simplex it is in.
+/// In 2d, this is the 3 corners of a triangle, and in 3d, the 4 corners of a tetrahedron.
+///
+/// Each corner is a grid point whose [`base`](GridPoint2::base) identifies the lattice point, so it
+/// can be seeded, and whose [`offset`](GridPoint2::offset) is the unskewed displacement from that
+/// lattice point to the input. That makes each corner ready for [`Seeding`](super::seeded::Seeding)
+/// and [`Perlin`](super::perlin::Perlin). See [`SimplexFalloff`] to combine the corners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimplexGrid {
+    /// the frequency of the lattice
+    pub frequency: f32,
+}
+
+impl SimplexGrid {
+    /// constructs a new [`SimplexGrid`] of this frequency
+    pub fn new_frequency(frequency: f32) -> Self {
+        Self { frequency }
+    }
+
+    /// constructs a new [`SimplexGrid`] of this period
+    pub fn new_period(period: f32) -> Self {
+        Self::new_frequency(1.0 / make_nonzero_f32(period))
+    }
+}
+
+impl From<Period> for SimplexGrid {
+    fn from(value: Period) -> Self {
+        Self::new_period(value.0)
+    }
+}
+
+/// The factor that skews 2d space onto the simplex lattice: (√3 - 1) / 2.
+pub(super) const SKEW_2D: f32 = 0.3660254;
+/// The factor that unskews the 2d simplex lattice back to normal space: (3 - √3) / 6.
+pub(super) const UNSKEW_2D: f32 = 0.21132487;
+/// The factor that skews 3d space onto the simplex lattice.
+const SKEW_3D: f32 = 1.0 / 3.0;
+/// The factor that unskews the 3d simplex lattice back to normal space.
+const UNSKEW_3D: f32 = 1.0 / 6.0;
+
+impl NoiseOp<Vec2> for SimplexGrid {
+    type Output = [GridPoint2; 3];
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let input = input * self.frequency;
+        let skewed = input + Vec2::splat(input.element_sum() * SKEW_2D);
+        let cell = skewed.floor();
+        let origin = cell - Vec2::splat(cell.element_sum() * UNSKEW_2D);
+        let offset = input - origin;
+        // the cell is split along its diagonal into two triangles.
+        let middle = if offset.x > offset.y {
+            IVec2::X
+        } else {
+            IVec2::Y
+        };
+        let cell = cell.as_ivec2();
+        [IVec2::ZERO, middle, IVec2::ONE].map(|corner| GridPoint2 {
+            base: (cell + corner).adapt::<UVec2>(),
+            offset: offset - corner.as_vec2()
+                + Vec2::splat(corner.element_sum() as f32 * UNSKEW_2D),
+        })
+    }
+}
+
+impl NoiseOp<Vec3> for SimplexGrid {
+    type Output = [GridPoint3; 4];
+
+    #[inline]
+    fn get(&self, input: Vec3) -> Self::Output {
+        let input = input * self.frequency;
+        let skewed = input + Vec3::splat(input.element_sum() * SKEW_3D);
+        let cell = skewed.floor();
+        let origin = cell - Vec3::splat(cell.element_sum() * UNSKEW_3D);
+        let offset = input - origin;
+        // the cell is split into six tetrahedrons, one for each ordering of the axes.
+        let (first, second) = if offset.x >= offset.y {
+            if offset.y >= offset.z {
+                (IVec3::X, IVec3::new(1, 1, 0))
+            } else if offset.x >= offset.z {
+                (IVec3::X, IVec3::new(1, 0, 1))
+            } else {
+                (IVec3::Z, IVec3::new(1, 0, 1))
+            }
+        } else if offset.y < offset.z {
+            (IVec3::Z, IVec3::new(0, 1, 1))
+        } else if offset.x < offset.z {
+            (IVec3::Y, IVec3::new(0, 1, 1))
+        } else {
+            (IVec3::Y, IVec3::new(1, 1, 0))
+        };
+        let cell = cell.as_ivec3();
+        [IVec3::ZERO, first, second, IVec3::ONE].map(|corner| GridPoint3 {
+            base: (cell + corner).adapt::<UVec3>(),
+            offset: offset - corner.as_vec3()
+                + Vec3::splat(corner.element_sum() as f32 * UNSKEW_3D),
+        })
+    }
+}
+
+/// A [`NoiseOp`] that combines the corners from a [`SimplexGrid`] into simplex noise.
+/// Each corner is passed to `N`, and the results are summed, weighted by a falloff that reaches 0
+/// before the corner's influence could cross into another simplex. This makes the result smooth.
+///
+/// The sum is scaled so that if `N` is [`Perlin`](super::perlin::Perlin) with a source whose
+/// gradients are unit length, like [`RuntimeRand`](super::perlin::RuntimeRand), the result stays
+/// in -1..=1.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SimplexFalloff<N>(pub N);
+
+/// easily implements [`SimplexFalloff`] for different dimensions
+macro_rules! impl_simplex_falloff {
+    ($point:ty, $corners:literal, $radius_sqr:literal, $scale:expr) => {
+        impl<N: NoiseOp<$point>> NoiseOp<[$point; $corners]> for SimplexFalloff<N>
+        where
+            N::Output: NoiseConverter<f32, Input = N::Output>,
+        {
+            type Output = f32;
+
+            #[inline]
+            fn get(&self, input: [$point; $corners]) -> Self::Output {
+                let mut total = 0.0;
+                for corner in input {
+                    let falloff: f32 = $radius_sqr - corner.offset.length_squared();
+                    if falloff > 0.0 {
+                        let falloff = falloff * falloff;
+                        total += falloff * falloff * self.0.get(corner).adapt::<f32>();
+                    }
+                }
+                total * $scale
+            }
+        }
+    };
+}
+
+// The classic scales are 70 and 32 for raw dot products, and `Perlin` multiplies those by 2 / √d.
+impl_simplex_falloff!(GridPoint2, 3, 0.5, 70.0 / core::f32::consts::SQRT_2);
+impl_simplex_falloff!(GridPoint3, 4, 0.6, 32.0 * 0.8660254); // √3 / 2
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::{
+        perlin::{
+            Perlin,
+            RuntimeRand,
+        },
+        seeded::Seeding,
+    };
+
+    /// Simplex perlin noise: seeds each corner and takes its perlin dot product.
+    struct SimplexPerlin(Seeding);
+
+    impl NoiseOp<GridPoint2> for SimplexPerlin {
+        type Output = f32;
+
+        fn get(&self, input: GridPoint2) -> Self::Output {
+            Perlin(RuntimeRand).get(self.0.get(input).map(|point| point.offset))
+        }
+    }
+
+    impl NoiseOp<GridPoint3> for SimplexPerlin {
+        type Output = f32;
+
+        fn get(&self, input: GridPoint3) -> Self::Output {
+            Perlin(RuntimeRand).get(self.0.get(input).map(|point| point.offset))
+        }
+    }
+
+    #[test]
+    fn simplex_corners_surround_input() {
+        let grid = SimplexGrid::new_frequency(1.0);
+        for i in 0..200 {
+            let x = i as f32 * 0.173 - 17.0;
+            let y = (i * 7 % 23) as f32 * 0.41 - 3.0;
+            let corners: [GridPoint2; 3] = grid.get(Vec2::new(x, y));
+            assert_ne!(corners[0].base, corners[1].base);
+            assert_ne!(corners[1].base, corners[2].base);
+            assert!(corners.iter().all(|corner| corner.offset.length() < 1.0));
+            assert!(
+                corners
+                    .iter()
+                    .any(|corner| corner.offset.length_squared() < 0.5)
+            );
+
+            let corners: [GridPoint3; 4] = grid.get(Vec3::new(x, y, x * 0.3 - y));
+            for (index, corner) in corners.iter().enumerate() {
+                assert!(corner.offset.length() < 1.0);
+                for other in &corners[index + 1..] {
+                    assert_ne!(corner.base, other.base);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simplex_noise_is_bounded() {
+        let grid = SimplexGrid::new_period(3.0);
+        let noise = SimplexFalloff(SimplexPerlin(Seeding(12)));
+        let mut largest = 0f32;
+        for y in -40..40 {
+            for x in -40..40 {
+                let input = Vec2::new(x as f32, y as f32) * 0.37;
+                let flat = noise.get(grid.get(input));
+                let deep = noise.get(grid.get(input.extend(y as f32 * 0.21)));
+                assert!(flat.abs() <= 1.0 && deep.abs() <= 1.0);
+                largest = largest.max(flat.abs()).max(deep.abs());
+            }
+        }
+        assert!(largest > 0.3);
+    }
+}
@@ -1,5 +1,12 @@
 //! This module allows arrays of noise to be combinned into one in various ways
 
+use core::ops::Mul;
+
+use bevy_math::{
+    BVec2,
+    Vec2,
+};
+
 use super::{
     NoiseOp,
     NoiseType,
@@ -19,10 +26,14 @@ use crate::spatial::{
     },
     interpolating::{
         Lerpable,
+        Linear,
         MixerFxn,
+        mix_1d,
     },
     square::{
         Axies2d,
+        Axis2d,
+        Corner2d,
         Corners2d,
     },
 };
@@ -80,6 +91,16 @@ pub struct LerpLocationOf;
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct LerpValuesOf;
 
+/// A [`NoiseOp`] that gets the per-axis interpolation weights of a [`LerpReady`] as a plain array.
+/// This is mostly useful for debugging.
+///
+/// Unlike [`LerpLocationOf`], which returns the [`LerpLocation`] as is, this passes each axis
+/// through the curve `C`, just like [`Smooth`] does. Using the same curve as the [`Smooth`] in
+/// question gives exactly the weights it will use. The default, [`Linear`], gives the location
+/// unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LerpWeightsOf<C = Linear>(pub C);
+
 /// Represents some data that is ready to be passed to [`Smooth`].
 pub type LerpReady<P, L> = Associated<LerpValues<P>, LerpLocation<L>>;
 
@@ -129,6 +150,25 @@ impl<P: NoiseType, L> AssociationMapping<LerpValuesOf> for LerpReady<P, L> {
     }
 }
 
+/// allows implementing [`LerpWeightsOf`] easily for different locations
+macro_rules! impl_lerp_weights {
+    ($a:ty, $d:literal) => {
+        impl<P: NoiseType, C: MixerFxn<f32, f32>> NoiseOp<LerpReady<P, $a>> for LerpWeightsOf<C> {
+            type Output = [f32; $d];
+
+            #[inline]
+            fn get(&self, input: LerpReady<P, $a>) -> Self::Output {
+                <[f32; $d]>::from(input.meta.0).map(|x| self.0.mix(x))
+            }
+        }
+    };
+}
+
+impl_lerp_weights!([f32; 1], 1);
+impl_lerp_weights!(Axies2d<f32>, 2);
+impl_lerp_weights!(Axies3d<f32>, 3);
+impl_lerp_weights!(Axies4d<f32>, 4);
+
 /// a noise type to smooth out grid noise
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Smooth<C>(pub C);
@@ -153,6 +193,373 @@ macro_rules! impl_smooth {
     };
 }
 
+impl<T: NoiseType + Lerpable<f32> + Copy, C: MixerFxn<f32, f32>>
+    NoiseOp<LerpReady<[T; 2], [f32; 1]>> for Smooth<C>
+{
+    type Output = T;
+
+    #[inline]
+    fn get(&self, input: LerpReady<[T; 2], [f32; 1]>) -> Self::Output {
+        let Associated {
+            value: LerpValues(extents),
+            meta: LerpLocation(location),
+        } = input;
+        mix_1d(&extents, location, &self.0)
+    }
+}
+
 impl_smooth!(interpolate_2d, Axies2d<f32>, Corners2d<T>);
 impl_smooth!(interpolate_3d, Axies3d<f32>, Corners3d<T>);
 impl_smooth!(interpolate_4d, Axies4d<f32>, Corners4d<T>);
+
+/// Like [`Smooth`], but gives the analytic gradient of the smoothed value instead of the value
+/// itself. The gradient is in units of the value per grid cell, one per axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothGradient<C>(pub C);
+
+/// A [`SmoothGradient`] that interpolates linearly.
+/// Note that the gradient of linear interpolation is not continuous across cells.
+pub type LerpGradient = SmoothGradient<Linear>;
+
+/// Combines [`Smooth`] and [`SmoothGradient`], giving the smoothed value and its gradient as
+/// `(value, gradient)`. This is cheaper than running both.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothAndGradient<C>(pub C);
+
+/// allows implementing [`SmoothGradient`] and [`SmoothAndGradient`] easily for different types
+macro_rules! impl_smooth_gradient {
+    ($gradient:ident, $both:ident, $a:ty, $s:ty, $g:ty) => {
+        impl<T: NoiseType + Lerpable<f32> + Mul<f32, Output = T> + Copy, C: MixerFxn<f32, f32>>
+            NoiseOp<LerpReady<$s, $a>> for SmoothGradient<C>
+        {
+            type Output = $g;
+
+            #[inline]
+            fn get(&self, input: LerpReady<$s, $a>) -> Self::Output {
+                let Associated {
+                    value: LerpValues(extents),
+                    meta: LerpLocation(location),
+                } = input;
+                extents.$gradient(location, &self.0)
+            }
+        }
+
+        impl<T: NoiseType + Lerpable<f32> + Mul<f32, Output = T> + Copy, C: MixerFxn<f32, f32>>
+            NoiseOp<LerpReady<$s, $a>> for SmoothAndGradient<C>
+        {
+            type Output = (T, $g);
+
+            #[inline]
+            fn get(&self, input: LerpReady<$s, $a>) -> Self::Output {
+                let Associated {
+                    value: LerpValues(extents),
+                    meta: LerpLocation(location),
+                } = input;
+                extents.$both(location, &self.0)
+            }
+        }
+    };
+}
+
+impl<T: NoiseType + Lerpable<f32> + Copy, C: MixerFxn<f32, f32>>
+    NoiseOp<LerpReady<[T; 2], [f32; 1]>> for SmoothGradient<C>
+{
+    type Output = [T; 1];
+
+    #[inline]
+    fn get(&self, input: LerpReady<[T; 2], [f32; 1]>) -> Self::Output {
+        let Associated {
+            value: LerpValues([start, end]),
+            meta: LerpLocation([t]),
+        } = input;
+        [start.mix_gradient(end, t, &self.0)]
+    }
+}
+
+impl<T: NoiseType + Lerpable<f32> + Copy, C: MixerFxn<f32, f32>>
+    NoiseOp<LerpReady<[T; 2], [f32; 1]>> for SmoothAndGradient<C>
+{
+    type Output = (T, [T; 1]);
+
+    #[inline]
+    fn get(&self, input: LerpReady<[T; 2], [f32; 1]>) -> Self::Output {
+        let Associated {
+            value: LerpValues(extents),
+            meta: LerpLocation(location),
+        } = input;
+        (
+            mix_1d(&extents, location, &self.0),
+            [extents[0].mix_gradient(extents[1], location[0], &self.0)],
+        )
+    }
+}
+
+impl_smooth_gradient!(
+    interpolate_gradient_2d,
+    interpolate_and_gradient_2d,
+    Axies2d<f32>,
+    Corners2d<T>,
+    Axies2d<T>
+);
+impl_smooth_gradient!(
+    interpolate_gradient_3d,
+    interpolate_and_gradient_3d,
+    Axies3d<f32>,
+    Corners3d<T>,
+    Axies3d<T>
+);
+impl_smooth_gradient!(
+    interpolate_gradient_4d,
+    interpolate_and_gradient_4d,
+    Axies4d<f32>,
+    Corners4d<T>,
+    Axies4d<T>
+);
+
+/// a noise type that blends the three values of a triangle by their barycentric weights, like those
+/// from a [`TriangleGrid`](super::grid::TriangleGrid)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothBary;
+
+impl<T: NoiseType + Lerpable<f32>> NoiseOp<Associated<[T; 3], [f32; 3]>> for SmoothBary {
+    type Output = T;
+
+    #[inline]
+    fn get(&self, input: Associated<[T; 3], [f32; 3]>) -> Self::Output {
+        let Associated {
+            value: [first, second, third],
+            meta: [first_weight, second_weight, third_weight],
+        } = input;
+        let rest = first_weight + second_weight;
+        let edge = if rest > 0.0 {
+            first.lerp_dirty(second, second_weight / rest)
+        } else {
+            first
+        };
+        edge.lerp_dirty(third, third_weight)
+    }
+}
+
+/// A noise type to smooth out grid noise whose extents carry both a value and a gradient, as
+/// `(value, gradient)` pairs. The result passes through each extent's value with its gradient,
+/// giving smoother transitions than [`Smooth`] without setting up perlin noise.
+///
+/// Gradients are in units of the value per grid cell. The values are blended by the curve `C`, so
+/// the result only matches the gradients exactly if `C` is flat at both ends, like
+/// [`Cubic`](crate::spatial::interpolating::Cubic). That makes this cubic hermite interpolation.
+/// In more than 1 dimension, the gradients across each axis are blended by `C` too.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothHermite<C>(pub C);
+
+impl<C: MixerFxn<f32, f32>> SmoothHermite<C> {
+    /// Interpolates from `start` to `end` by `t`, where each is a value and its gradient.
+    #[inline]
+    fn hermite(&self, start: (f32, f32), end: (f32, f32), t: f32) -> f32 {
+        let inv_t = 1.0 - t;
+        start.0 + (end.0 - start.0) * self.0.mix(t) + start.1 * t * inv_t * inv_t
+            - end.1 * t * t * inv_t
+    }
+}
+
+impl<C: MixerFxn<f32, f32>> NoiseOp<LerpReady<[(f32, f32); 2], [f32; 1]>> for SmoothHermite<C> {
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: LerpReady<[(f32, f32); 2], [f32; 1]>) -> Self::Output {
+        let Associated {
+            value: LerpValues([start, end]),
+            meta: LerpLocation([t]),
+        } = input;
+        self.hermite(start, end, t)
+    }
+}
+
+impl<C: MixerFxn<f32, f32>> NoiseOp<LerpReady<Corners2d<(f32, Vec2)>, Axies2d<f32>>>
+    for SmoothHermite<C>
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: LerpReady<Corners2d<(f32, Vec2)>, Axies2d<f32>>) -> Self::Output {
+        let Associated {
+            value: LerpValues(corners),
+            meta: LerpLocation(location),
+        } = input;
+        let x = location[Axis2d::X];
+        let y = location[Axis2d::Y];
+        let blend = self.0.mix(x);
+        // interpolate along x on the bottom and top edges, keeping the gradient along y.
+        let edge = |left: (f32, Vec2), right: (f32, Vec2)| {
+            (
+                self.hermite((left.0, left.1.x), (right.0, right.1.x), x),
+                left.1.y + (right.1.y - left.1.y) * blend,
+            )
+        };
+        let down = edge(corners[Corner2d::Ld], corners[Corner2d::Rd]);
+        let up = edge(corners[Corner2d::Lu], corners[Corner2d::Ru]);
+        self.hermite(down, up, y)
+    }
+}
+
+/// Like [`Smooth`], but also reports which corner dominated the result, the one with the largest
+/// interpolation weight, as metadata. This is useful for flat or cel shading.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothWithDominantCorner<C>(pub C);
+
+impl<T: NoiseType + Lerpable<f32> + Copy, C: MixerFxn<f32, f32>>
+    NoiseOp<LerpReady<Corners2d<T>, Axies2d<f32>>> for SmoothWithDominantCorner<C>
+{
+    type Output = Associated<T, Corner2d>;
+
+    #[inline]
+    fn get(&self, input: LerpReady<Corners2d<T>, Axies2d<f32>>) -> Self::Output {
+        let Associated {
+            value: LerpValues(extents),
+            meta: LerpLocation(location),
+        } = input;
+        let weights = location.map(|x| self.0.mix(x));
+        Associated {
+            value: extents.interpolate_2d(location, &self.0),
+            meta: Corner2d::from_signs(BVec2::new(
+                weights[Axis2d::X] >= 0.5,
+                weights[Axis2d::Y] >= 0.5,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{
+        Vec2,
+        Vec3,
+    };
+
+    use super::*;
+    use crate::{
+        noise::{
+            grid::GridNoise,
+            norm::UNorm,
+            seeded::Seeding,
+        },
+        spatial::interpolating::Cubic,
+    };
+
+    #[test]
+    fn lerp_weights_match_offset() {
+        let grid = GridNoise::new_frequency(1.0);
+        for sample in [
+            Vec3::new(0.25, 0.5, 0.75),
+            Vec3::new(-3.1, 8.6, 2.0),
+            Vec3::new(100.9, -0.4, 7.3),
+        ] {
+            let point = grid.get(sample);
+            let offset = point.offset.to_array();
+            let ready = Lerp.get(point);
+            assert_eq!(
+                LerpWeightsOf::<Linear>::default().get(ready.clone()),
+                offset
+            );
+            assert_eq!(
+                LerpWeightsOf(Cubic).get(ready),
+                offset.map(|x| <Cubic as MixerFxn<f32, f32>>::mix(&Cubic, x))
+            );
+        }
+    }
+
+    #[test]
+    fn value_noise_1d_is_continuous() {
+        let grid = GridNoise::new_period(4.0);
+        let seeding = Seeding(7);
+        let noise = |x: f32| {
+            let ready = Lerp.get(grid.get(x)).map(|LerpValues(ends)| {
+                LerpValues(ends.map(|end| seeding.get(end).rng(0).adapt::<UNorm>().adapt::<f32>()))
+            });
+            Smooth(Cubic).get(ready)
+        };
+        for boundary in [-8.0, 0.0, 4.0, 12.0] {
+            let below = noise(boundary - 1e-3);
+            let above = noise(boundary + 1e-3);
+            assert!((below - above).abs() < 1e-3);
+        }
+        assert_ne!(noise(1.0), noise(5.0));
+    }
+
+    #[test]
+    fn smooth_gradient_matches_finite_difference() {
+        let grid = GridNoise::new_frequency(1.0);
+        let seeding = Seeding(11);
+        let ready = |input: Vec2| {
+            Lerp.get(grid.get(input)).map(|LerpValues(corners)| {
+                LerpValues(
+                    corners
+                        .map(|corner| seeding.get(corner).rng(0).adapt::<UNorm>().adapt::<f32>()),
+                )
+            })
+        };
+        let step = 1e-3;
+        for input in [
+            Vec2::new(0.3, 0.6),
+            Vec2::new(-4.2, 7.9),
+            Vec2::new(12.55, -3.1),
+        ] {
+            let (value, gradient) = SmoothAndGradient(Cubic).get(ready(input));
+            assert_eq!(value, Smooth(Cubic).get(ready(input)));
+            assert_eq!(gradient, SmoothGradient(Cubic).get(ready(input)));
+            for (axis, offset) in [(Axis2d::X, Vec2::X), (Axis2d::Y, Vec2::Y)] {
+                let ahead: f32 = Smooth(Cubic).get(ready(input + offset * step));
+                let behind: f32 = Smooth(Cubic).get(ready(input - offset * step));
+                let estimate = (ahead - behind) / (2.0 * step);
+                assert!((gradient[axis] - estimate).abs() < 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn hermite_matches_endpoints() {
+        let start = (0.25, 3.0);
+        let end = (-1.0, -0.5);
+        let curve = |t: f32| {
+            SmoothHermite(Cubic).get(LerpReady {
+                value: LerpValues([start, end]),
+                meta: LerpLocation([t]),
+            })
+        };
+        assert!((curve(0.0) - start.0).abs() < 1e-6);
+        assert!((curve(1.0) - end.0).abs() < 1e-6);
+        let step = 1e-3;
+        assert!(((curve(step) - curve(0.0)) / step - start.1).abs() < 1e-2);
+        assert!(((curve(1.0) - curve(1.0 - step)) / step - end.1).abs() < 1e-2);
+
+        // the 2d version follows the corners' gradients along its edges.
+        let corners = Corners2d([
+            (0.0, Vec2::new(1.0, 2.0)),
+            (1.0, Vec2::new(0.0, -1.0)),
+            (0.5, Vec2::new(-2.0, 0.0)),
+            (0.0, Vec2::new(1.0, 1.0)),
+        ]);
+        let surface = |x: f32, y: f32| {
+            SmoothHermite(Cubic).get(LerpReady {
+                value: LerpValues(corners),
+                meta: LerpLocation(Axies2d([x, y])),
+            })
+        };
+        assert!((surface(0.0, 1.0) - corners[Corner2d::Lu].0).abs() < 1e-6);
+        assert!((surface(1.0, 0.0) - corners[Corner2d::Rd].0).abs() < 1e-6);
+        let gradient = Vec2::new(
+            surface(step, 0.0) - surface(0.0, 0.0),
+            surface(0.0, step) - surface(0.0, 0.0),
+        ) / step;
+        assert!(gradient.abs_diff_eq(corners[Corner2d::Ld].1, 1e-2));
+    }
+
+    #[test]
+    fn dominant_corner_is_nearest() {
+        let grid = GridNoise::new_frequency(1.0);
+        let ready = Lerp.get(grid.get(Vec2::new(3.9, 7.8)));
+        let ready =
+            ready.map(|LerpValues(corners)| LerpValues(corners.map(|corner| corner.base.x as f32)));
+        let result = SmoothWithDominantCorner(Cubic).get(ready);
+        assert_eq!(result.meta, Corner2d::Ru);
+    }
+}
@@ -0,0 +1,346 @@
+//! This module contains noise operations that tile, repeat, warp, or break up the repetition of
+//! the domain of other noise.
+
+use bevy_math::{
+    BVec2,
+    IVec2,
+    UVec2,
+    Vec2,
+    Vec3,
+    Vec4,
+};
+
+use super::{
+    NoiseOp,
+    NoiseType,
+    conversions::NoiseConverter,
+    norm::{
+        UNorm,
+        make_nonzero_f32,
+    },
+    white::White32,
+};
+use crate::spatial::{
+    interpolating::{
+        Cubic,
+        MixerFxn,
+    },
+    square::Axis2d,
+};
+
+/// A [`NoiseOp`] that breaks up repetition with stochastic tiling, as described by Heitz and
+/// Neyret. The domain is split into a grid of triangles, where each vertex of the grid translates
+/// `N` by a random offset. Each sample blends `N` at the three offsets of the vertices of its
+/// triangle, so the result never repeats, even if `N` does.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticTile<N> {
+    /// The size of the triangles, in the units of the input.
+    pub tile_scale: f32,
+    /// The seed of the random offsets.
+    pub seed: u32,
+    /// The noise to tile.
+    pub noise: N,
+}
+
+impl<N> StochasticTile<N> {
+    /// The maximum distance `N` is translated by on each axis.
+    pub const MAX_OFFSET: f32 = 1024.0;
+
+    /// Computes the three offsets that `N` is sampled at relative to `input` and their blending
+    /// weights, which sum to 1.
+    #[inline]
+    pub fn tiles(&self, input: Vec2) -> [(Vec2, f32); 3] {
+        // skew the input onto a grid of equilateral triangles.
+        let scaled = input / self.tile_scale * 2.0 * 1.7320508; // 2 * sqrt 3
+        let skewed = Vec2::new(scaled.x, scaled.y * 1.1547005 - scaled.x * 0.57735027);
+        let base = skewed.floor();
+        let fract = skewed - base;
+        let base = base.as_ivec2();
+
+        let z = 1.0 - fract.x - fract.y;
+        let vertices = if z > 0.0 {
+            [
+                (base, z),
+                (base + IVec2::Y, fract.y),
+                (base + IVec2::X, fract.x),
+            ]
+        } else {
+            [
+                (base + IVec2::ONE, -z),
+                (base + IVec2::X, 1.0 - fract.y),
+                (base + IVec2::Y, 1.0 - fract.x),
+            ]
+        };
+        vertices.map(|(vertex, weight)| (self.offset_of(vertex), weight))
+    }
+
+    #[inline]
+    fn offset_of(&self, vertex: IVec2) -> Vec2 {
+        let vertex = vertex.adapt::<UVec2>();
+        let rng = White32(self.seed);
+        Vec2::new(
+            rng.get([vertex.x, vertex.y, 0])
+                .adapt::<UNorm>()
+                .adapt::<f32>(),
+            rng.get([vertex.x, vertex.y, 1])
+                .adapt::<UNorm>()
+                .adapt::<f32>(),
+        ) * Self::MAX_OFFSET
+    }
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for StochasticTile<N>
+where
+    N::Output: NoiseConverter<f32, Input = N::Output>,
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        self.tiles(input)
+            .into_iter()
+            .map(|(offset, weight)| self.noise.get(input + offset).adapt::<f32>() * weight)
+            .sum()
+    }
+}
+
+/// A [`NoiseOp`] that joins two noises along a seam, so tiles of different noise can meet without a
+/// visible edge. The seam is where the [`seam`](Self::seam) axis of the input is 0. `A` is on the
+/// negative side and `B` is on the positive side, and they are blended with a [`Cubic`] ramp in a
+/// band [`width`](Self::width) wide, centered on the seam. Outside the band, only one is sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeamJoin<A, B> {
+    /// The axis that crosses the seam.
+    pub seam: Axis2d,
+    /// The width of the band where `A` and `B` blend.
+    pub width: f32,
+    /// The noise on the negative side of the seam.
+    pub a: A,
+    /// The noise on the positive side of the seam.
+    pub b: B,
+}
+
+impl<A: NoiseOp<Vec2>, B: NoiseOp<Vec2>> NoiseOp<Vec2> for SeamJoin<A, B>
+where
+    A::Output: NoiseConverter<f32, Input = A::Output>,
+    B::Output: NoiseConverter<f32, Input = B::Output>,
+{
+    type Output = f32;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        let across = input[self.seam.get_index() as usize];
+        let t = (across / make_nonzero_f32(self.width) + 0.5).clamp(0.0, 1.0);
+        if t <= 0.0 {
+            return self.a.get(input).adapt::<f32>();
+        }
+        if t >= 1.0 {
+            return self.b.get(input).adapt::<f32>();
+        }
+        let a = self.a.get(input).adapt::<f32>();
+        let b = self.b.get(input).adapt::<f32>();
+        let t: f32 = Cubic.mix(t);
+        a + (b - a) * t
+    }
+}
+
+/// A [`NoiseOp`] that mirrors the domain of `N` across the chosen axes, which is useful for
+/// symmetric, kaleidoscope-like patterns. Each selected axis of the input is made positive before
+/// sampling `N`, so `N` is reflected across that axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorDomain<N> {
+    /// Which axes to mirror across. Mirroring `x` reflects the input across the y axis.
+    pub axes: BVec2,
+    /// The noise to mirror.
+    pub noise: N,
+}
+
+impl<N: NoiseOp<Vec2>> NoiseOp<Vec2> for MirrorDomain<N> {
+    type Output = N::Output;
+
+    #[inline]
+    fn get(&self, input: Vec2) -> Self::Output {
+        self.noise.get(Vec2::select(self.axes, input.abs(), input))
+    }
+}
+
+/// A [`NoiseOp`] that warps its input by a vector noise `N`. The input is offset by the output of
+/// `N` at the input, scaled by [`strength`](Self::strength), and the warped input is the result,
+/// so it can be passed on to the noise being warped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DomainWarp<N> {
+    /// How far the input is offset per unit of output from `N`.
+    pub strength: f32,
+    /// The vector noise that warps the input.
+    pub noise: N,
+}
+
+/// easily implements [`DomainWarp`] for different vectors
+macro_rules! impl_domain_warp {
+    ($vec:ty) => {
+        impl<N: NoiseOp<$vec, Output = $vec>> NoiseOp<$vec> for DomainWarp<N> {
+            type Output = $vec;
+
+            #[inline]
+            fn get(&self, input: $vec) -> Self::Output {
+                input + self.noise.get(input) * self.strength
+            }
+        }
+    };
+}
+
+impl_domain_warp!(Vec2);
+impl_domain_warp!(Vec3);
+impl_domain_warp!(Vec4);
+
+/// A [`NoiseOp`] that rounds its input to the nearest multiple of [`grid`](Self::grid) before
+/// sampling `N`. Inputs that drift slightly, like from floating point error between frames, snap
+/// to the same point and give exactly the same output. A grid of 0.0 or less leaves the input
+/// unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapInput<N> {
+    /// The spacing of the points the input snaps to.
+    pub grid: f32,
+    /// The noise to sample at the snapped input.
+    pub noise: N,
+}
+
+/// easily implements [`SnapInput`] for different inputs
+macro_rules! impl_snap_input {
+    ($t:ty) => {
+        impl<N: NoiseOp<$t>> NoiseOp<$t> for SnapInput<N> {
+            type Output = N::Output;
+
+            #[inline]
+            fn get(&self, input: $t) -> Self::Output {
+                if self.grid > 0.0 {
+                    self.noise.get((input / self.grid).round() * self.grid)
+                } else {
+                    self.noise.get(input)
+                }
+            }
+        }
+    };
+}
+
+impl_snap_input!(f32);
+impl_snap_input!(Vec2);
+impl_snap_input!(Vec3);
+impl_snap_input!(Vec4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stochastic_tile_weights_sum_to_one() {
+        let tile = StochasticTile {
+            tile_scale: 3.0,
+            seed: 9,
+            noise: (),
+        };
+        for y in -20..20 {
+            for x in -20..20 {
+                let tiles = tile.tiles(Vec2::new(x as f32, y as f32) * 0.37);
+                let total: f32 = tiles.iter().map(|(_, weight)| weight).sum();
+                assert!((total - 1.0).abs() < 1e-5);
+                assert!(tiles.iter().all(|(_, weight)| *weight >= 0.0));
+            }
+        }
+    }
+
+    /// A plane sloping by this much along each axis.
+    struct Ramp(Vec2);
+
+    impl NoiseOp<Vec2> for Ramp {
+        type Output = f32;
+
+        fn get(&self, input: Vec2) -> Self::Output {
+            input.dot(self.0)
+        }
+    }
+
+    #[test]
+    fn seam_join_keeps_each_side() {
+        let a = Ramp(Vec2::new(0.3, 1.0));
+        let b = Ramp(Vec2::new(-2.0, 0.5));
+        let join = SeamJoin {
+            seam: Axis2d::X,
+            width: 2.0,
+            a: Ramp(a.0),
+            b: Ramp(b.0),
+        };
+        for y in [-3.0, 0.0, 4.5] {
+            for x in [-10.0, -1.5] {
+                let input = Vec2::new(x, y);
+                assert_eq!(join.get(input), a.get(input));
+            }
+            for x in [1.5, 10.0] {
+                let input = Vec2::new(x, y);
+                assert_eq!(join.get(input), b.get(input));
+            }
+            let middle = Vec2::new(0.0, y);
+            let blended = (a.get(middle) + b.get(middle)) * 0.5;
+            assert!((join.get(middle) - blended).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn mirror_domain_is_symmetric() {
+        let mirror = MirrorDomain {
+            axes: BVec2::new(true, false),
+            noise: Ramp(Vec2::new(0.3, 1.0)),
+        };
+        for (a, b) in [(1.5, 2.0), (0.3, -7.25), (12.0, 0.5)] {
+            assert_eq!(mirror.get(Vec2::new(-a, b)), mirror.get(Vec2::new(a, b)));
+            assert_ne!(mirror.get(Vec2::new(a, -b)), mirror.get(Vec2::new(a, b)));
+        }
+    }
+
+    /// Warps every input by the same amount.
+    struct Shift(Vec3);
+
+    impl NoiseOp<Vec3> for Shift {
+        type Output = Vec3;
+
+        fn get(&self, _input: Vec3) -> Self::Output {
+            self.0
+        }
+    }
+
+    #[test]
+    fn snapped_inputs_match() {
+        let snap = SnapInput {
+            grid: 0.25,
+            noise: Ramp(Vec2::new(1.0, 3.0)),
+        };
+        for i in -20..20 {
+            let point = Vec2::new(i as f32 * 0.25, (i * 7 % 5) as f32 * 0.25);
+            let expected = snap.get(point);
+            assert_eq!(expected, snap.noise.get(point));
+            for drift in [
+                Vec2::new(0.12, -0.1),
+                Vec2::new(-0.01, 0.0),
+                Vec2::splat(1e-4),
+            ] {
+                assert_eq!(snap.get(point + drift), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn domain_warp_shifts_input() {
+        let input = Vec3::new(1.5, -2.0, 7.25);
+        let still = DomainWarp {
+            strength: 0.0,
+            noise: Shift(Vec3::new(1.0, 2.0, -1.0)),
+        };
+        assert_eq!(still.get(input), input);
+
+        let warped = DomainWarp {
+            strength: 0.5,
+            noise: Shift(Vec3::new(1.0, 2.0, -1.0)),
+        };
+        assert_eq!(warped.get(input), Vec3::new(2.0, -1.0, 6.75));
+    }
+}
@@ -3,6 +3,9 @@
 use std::marker::PhantomData;
 
 use bevy_math::{
+    UVec2,
+    UVec3,
+    UVec4,
     Vec2,
     Vec3,
     Vec4,
@@ -16,22 +19,29 @@ use super::{
         GridPoint2,
         GridPoint3,
         GridPoint4,
+        WrappingGridPoint,
     },
     merging::{
         ChebyshevDistance,
         EuclideanDistance,
         HybridDistance,
-        ManhatanDistance,
+        ManhattanDistance,
         Merger,
         MinIndex,
+        MinOrdersN,
+        MinkowskiDistance,
         Orderer,
     },
-    norm::UNorm,
+    norm::{
+        UNorm,
+        make_nonzero_f32,
+    },
     nudges::Nudge,
     seeded::{
         Seeded,
         Seeding,
     },
+    white::White32,
 };
 use crate::spatial::{
     cube::{
@@ -48,7 +58,7 @@ use crate::spatial::{
     },
 };
 
-/// Describes a source of Worly noise with a [`NoiseOp`] for [`VoronoiGraph`].
+/// Describes a source of Worley noise with a [`NoiseOp`] for [`VoronoiGraph`].
 pub trait VoronoiSource<const DIMENSIONS: u8, const APPROX: bool> {
     /// The type of noise
     type Noise;
@@ -57,8 +67,8 @@ pub trait VoronoiSource<const DIMENSIONS: u8, const APPROX: bool> {
     fn build_noise(self, max_nudge: f32) -> Self::Noise;
 }
 
-/// Worly noise is defined as any kind of noise derived from [`Cellular`] noise via a
-/// [`WorlyNoiseSource`] as `T`.
+/// Worley noise is defined as any kind of noise derived from [`Cellular`] noise via a
+/// [`VoronoiSource`] as `S`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Voronoi<
     const DIMENSIONS: u8,
@@ -115,34 +125,76 @@ impl<const DIMENSIONS: u8, const APPROX: bool, S: VoronoiSource<DIMENSIONS, APPR
     }
 }
 
-/// Defines a particular mode for `Worly` to operate in.
-pub trait WorlyMode {
-    /// Computes the actual worly result given an orderer and the points.
-    fn compute_worly<const N: usize, T: NoiseType>(
+/// Defines a particular mode for `Worley` to operate in.
+pub trait WorleyMode {
+    /// Computes the actual worley result given an orderer and the points.
+    fn compute_worley<const N: usize, T: NoiseType>(
         &self,
         orderer: &impl Orderer<T, OrderingOutput = UNorm>,
         points: [T; N],
     ) -> UNorm;
 }
 
-/// Allows for standard, distance-based worly noise.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct WorlyNoise<T, M>(T, M);
+/// The old, misspelled name of [`WorleyMode`]. Its method was also renamed, from
+/// `compute_worly` to [`compute_worley`](WorleyMode::compute_worley).
+#[deprecated(note = "renamed to `WorleyMode`")]
+pub use WorleyMode as WorlyMode;
 
-/// A [`VoronoiSource`] for [`WorlyNoise`].
+/// Allows for standard, distance-based worley noise.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorleyNoise<T, M>(T, M);
+
+/// The old, misspelled name of [`WorleyNoise`].
+#[deprecated(note = "renamed to `WorleyNoise`")]
+pub type WorlyNoise<T, M> = WorleyNoise<T, M>;
+
+/// A [`VoronoiSource`] for [`WorleyNoise`].
+///
+/// Distances are measured in units of grid cells, not in the space of the original input, so the
+/// output does not depend on the period of the grid. This makes it safe to blend worley noise of
+/// different frequencies without rescaling it, since the nearest distance is already divided by
+/// the cell size.
+///
+/// For example:
+/// ```
+/// # use bevy_math::Vec2;
+/// # use noiz::noise::{
+/// #     NoiseOp,
+/// #     NoiseType,
+/// #     grid::GridNoise,
+/// #     merging::ManhattanDistance,
+/// #     voronoi::{
+/// #         Voronoi,
+/// #         Worley,
+/// #         worley_mode::Nearest,
+/// #     },
+/// # };
+/// let grid = GridNoise::new_period(8.0);
+/// let worley = Voronoi::<2, Worley<ManhattanDistance, Nearest>>::full_default(7);
+/// let value: f32 = worley.get(grid.get(Vec2::new(3.0, -1.5))).adapt();
+/// assert!((0.0..=1.0).contains(&value));
+/// ```
 #[derive(Debug, Clone, Copy)]
-pub struct Worly<T, M> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+pub struct Worley<T, M> {
     /// marker data
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
     pub marker: PhantomData<T>,
-    /// This a a multiplier for the expected maximum length of a voronoi sphere.
-    /// 1.0 is the default. Infreasing this too much can lead to articacts.
-    /// Decreasing this can mave the voronoi spheres more issolated.
+    /// This is a multiplier for the expected maximum length of a voronoi sphere.
+    /// 1.0 is the default. Increasing this too much can lead to artifacts.
+    /// Decreasing this can make the voronoi spheres more isolated.
     pub expected_length_multiplier: f32,
-    /// Defines the [`WorlyMode`] this noise will use.
+    /// Defines the [`WorleyMode`] this noise will use.
     pub mode: M,
 }
 
-impl<T, M: Default> Default for Worly<T, M> {
+/// The old, misspelled name of [`Worley`].
+#[deprecated(note = "renamed to `Worley`")]
+pub type Worly<T, M> = Worley<T, M>;
+
+impl<T, M: Default> Default for Worley<T, M> {
     fn default() -> Self {
         Self {
             marker: PhantomData,
@@ -152,12 +204,12 @@ impl<T, M: Default> Default for Worly<T, M> {
     }
 }
 
-impl<T, M> Worly<T, M> {
+impl<T, M> Worley<T, M> {
     /// A version of [`shrunk_by`](Self::shrunk_by) that supplies a mode.
-    pub fn new_shrunk_by(srkinging_factor: f32, mode: M) -> Self {
+    pub fn new_shrunk_by(shrinking_factor: f32, mode: M) -> Self {
         Self {
             marker: PhantomData,
-            expected_length_multiplier: srkinging_factor.abs().clamp(0.0, 1.0),
+            expected_length_multiplier: shrinking_factor.abs().clamp(0.0, 1.0),
             mode,
         }
     }
@@ -171,24 +223,24 @@ impl<T, M> Worly<T, M> {
         }
     }
 
-    /// Sets the [`WorlyMode`] for this noise
+    /// Sets the [`WorleyMode`] for this noise
     pub fn with_mode(mut self, mode: M) -> Self {
         self.mode = mode;
         self
     }
 }
 
-impl<T, M: Default> Worly<T, M> {
-    /// Clams the absolute value of this factor as [`WorlySource::expected_length_multiplier`].
-    pub fn shrunk_by(srkinging_factor: f32) -> Self {
+impl<T, M: Default> Worley<T, M> {
+    /// Clamps the absolute value of this factor as [`Worley::expected_length_multiplier`].
+    pub fn shrunk_by(shrinking_factor: f32) -> Self {
         Self {
             marker: PhantomData,
-            expected_length_multiplier: srkinging_factor.abs().clamp(0.0, 1.0),
+            expected_length_multiplier: shrinking_factor.abs().clamp(0.0, 1.0),
             mode: M::default(),
         }
     }
 
-    /// Maxes the absolute value of this factor as [`WorlySource::expected_length_multiplier`].
+    /// Maxes the absolute value of this factor as [`Worley::expected_length_multiplier`].
     ///
     /// # Warning. This can lead to artifacts. Use this carefully.
     pub fn expanded_by(expansion_factor: f32) -> Self {
@@ -200,9 +252,9 @@ impl<T, M: Default> Worly<T, M> {
     }
 }
 
-/// Contains some common [`WorlyMode`]s.
-pub mod worly_mode {
-    use super::WorlyMode;
+/// Contains some common [`WorleyMode`]s.
+pub mod worley_mode {
+    use super::WorleyMode;
     use crate::noise::{
         NoiseType,
         merging::{
@@ -214,12 +266,14 @@ pub mod worly_mode {
         norm::UNorm,
     };
 
-    /// A [`WorlyMode`] that uses the nearst distance.
+    /// A [`WorleyMode`] that uses the nearest distance.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct Nearest;
 
-    impl WorlyMode for Nearest {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for Nearest {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -228,12 +282,14 @@ pub mod worly_mode {
         }
     }
 
-    /// A [`WorlyMode`] that uses the second nearst distance.
+    /// A [`WorleyMode`] that uses the second nearest distance.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct NextNearest;
 
-    impl WorlyMode for NextNearest {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for NextNearest {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -242,12 +298,43 @@ pub mod worly_mode {
         }
     }
 
-    /// A [`WorlyMode`] that subtracts the nearst distance from the second nearest.
+    /// A [`WorleyMode`] that uses the distance to the `N`th nearest point, where 0 is the nearest.
+    /// If there are not enough points, this will be 1.0.
+    #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
+    pub struct NthNearest<const N: usize>;
+
+    impl<const N: usize> WorleyMode for NthNearest<N> {
+        fn compute_worley<const K: usize, T: NoiseType>(
+            &self,
+            orderer: &impl Orderer<T, OrderingOutput = UNorm>,
+            points: [T; K],
+        ) -> UNorm {
+            // We need the N + 1 smallest orderings, but that can't be an array length, so the
+            // last one is kept separately.
+            let mut nearer = [f32::INFINITY; N];
+            let mut nth = f32::INFINITY;
+            for point in points {
+                let mut weight = orderer.ordering_of(&point);
+                for slot in nearer.iter_mut().chain(core::iter::once(&mut nth)) {
+                    if weight < *slot {
+                        core::mem::swap(&mut weight, slot);
+                    }
+                }
+            }
+            orderer.relative_ordering(nth)
+        }
+    }
+
+    /// A [`WorleyMode`] that subtracts the nearest distance from the second nearest.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct Difference;
 
-    impl WorlyMode for Difference {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for Difference {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -259,12 +346,14 @@ pub mod worly_mode {
         }
     }
 
-    /// A [`WorlyMode`] that averages the two nearst distances.
+    /// A [`WorleyMode`] that averages the two nearest distances.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct Average;
 
-    impl WorlyMode for Average {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for Average {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -276,12 +365,14 @@ pub mod worly_mode {
         }
     }
 
-    /// A [`WorlyMode`] that multiplies the nearst distance from the second nearest.
+    /// A [`WorleyMode`] that multiplies the nearest distance from the second nearest.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct Product;
 
-    impl WorlyMode for Product {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for Product {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -293,12 +384,14 @@ pub mod worly_mode {
         }
     }
 
-    /// A [`WorlyMode`] that divides the nearst distance by the second nearest.
+    /// A [`WorleyMode`] that divides the nearest distance by the second nearest.
     #[derive(Debug, Clone, Copy, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "reflect", derive(bevy_reflect::Reflect))]
     pub struct Ratio;
 
-    impl WorlyMode for Ratio {
-        fn compute_worly<const N: usize, T: NoiseType>(
+    impl WorleyMode for Ratio {
+        fn compute_worley<const N: usize, T: NoiseType>(
             &self,
             orderer: &impl Orderer<T, OrderingOutput = UNorm>,
             points: [T; N],
@@ -311,6 +404,116 @@ pub mod worly_mode {
     }
 }
 
+/// The old, misspelled name of [`worley_mode`].
+#[deprecated(note = "renamed to `worley_mode`")]
+pub mod worly_mode {
+    pub use super::worley_mode::{
+        Average,
+        Difference,
+        Nearest,
+        NextNearest,
+        Product,
+        Ratio,
+    };
+}
+
+/// A [`VoronoiSource`] that is low at the center of each cell and rises towards its edges with an
+/// inverse-square falloff, like the walls of soap bubbles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bubbles {
+    /// How quickly the value rises away from the center of a cell. Higher values make thicker
+    /// walls.
+    pub falloff: f32,
+}
+
+/// The [`NoiseOp`] produced by [`Bubbles`].
+#[derive(Debug, Clone, Copy)]
+pub struct BubblesNoise {
+    nearest: WorleyNoise<EuclideanDistance, worley_mode::Nearest>,
+    falloff: f32,
+}
+
+impl<I> NoiseOp<I> for BubblesNoise
+where
+    WorleyNoise<EuclideanDistance, worley_mode::Nearest>: NoiseOp<I, Output = UNorm>,
+{
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: I) -> Self::Output {
+        let distance = self.nearest.get(input).adapt::<f32>();
+        let scaled = distance * distance * self.falloff;
+        UNorm::new_clamped(scaled / (1.0 + scaled))
+    }
+}
+
+/// A [`VoronoiSource`] for [`WorleyNoise`] with [`MinkowskiDistance`]. This is separate from
+/// [`Worley`] because the distance needs its power, `p`.
+#[derive(Debug, Clone, Copy)]
+pub struct MinkowskiWorley<M> {
+    /// The power of the [`MinkowskiDistance`]. This must be positive, and values less than 1
+    /// produce concave cells.
+    pub p: f32,
+    /// This is a multiplier for the expected maximum length of a voronoi sphere, like in
+    /// [`Worley`].
+    pub expected_length_multiplier: f32,
+    /// Defines the [`WorleyMode`] this noise will use.
+    pub mode: M,
+}
+
+impl<M: Default> MinkowskiWorley<M> {
+    /// Creates a new [`MinkowskiWorley`] with this `p` and the default mode.
+    pub fn new(p: f32) -> Self {
+        Self {
+            p,
+            expected_length_multiplier: 1.0,
+            mode: M::default(),
+        }
+    }
+}
+
+/// A [`VoronoiSource`] that finds the four nearest [`EuclideanDistance`]s at once, packed into a
+/// [`Vec4`] from nearest to furthest. Each distance is normalized like [`Worley`], so the further
+/// distances are more likely to be clamped to 1.0. Increase the
+/// [`expected_length_multiplier`](Self::expected_length_multiplier) to see more of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorleyF1F4 {
+    /// This is a multiplier for the expected maximum length of a voronoi sphere, like in
+    /// [`Worley`].
+    pub expected_length_multiplier: f32,
+}
+
+impl Default for WorleyF1F4 {
+    fn default() -> Self {
+        Self {
+            expected_length_multiplier: 1.0,
+        }
+    }
+}
+
+/// The [`NoiseOp`] produced by [`WorleyF1F4`].
+#[derive(Debug, Clone, Copy)]
+pub struct WorleyF1F4Noise(EuclideanDistance);
+
+/// A [`VoronoiSource`] that blends between [`EuclideanDistance`] and [`ManhattanDistance`] worley
+/// noise, sharing the same cells. The [`NoiseOp`] `C` is sampled at the same grid point as the
+/// voronoi to decide the blend: 0 is purely euclidean, and 1 is purely manhattan.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlendedWorley<C, M = worley_mode::Nearest> {
+    /// The [`NoiseOp`] that controls the blend.
+    pub control: C,
+    /// Defines the [`WorleyMode`] both metrics will use.
+    pub mode: M,
+}
+
+/// The [`NoiseOp`] produced by [`BlendedWorley`].
+#[derive(Debug, Clone, Copy)]
+pub struct BlendedWorleyNoise<C, M> {
+    euclidean: WorleyNoise<EuclideanDistance, M>,
+    manhattan: WorleyNoise<ManhattanDistance, M>,
+    control: C,
+}
+
 /// Allows simple, nearest neighbor cellular noise
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CellularNoise<T>(T);
@@ -325,11 +528,241 @@ impl<T> Default for Cellular<T> {
     }
 }
 
+/// A [`VoronoiSource`] that gives each cell a constant random value, based on the seed of the
+/// nearest cell. This is useful for coloring regions. See also [`SoftRegionValue`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CellValue;
+
+impl<const DIMENSIONS: u8, const APPROX: bool> VoronoiSource<DIMENSIONS, APPROX> for CellValue {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        Self
+    }
+}
+
+/// A 2d [`VoronoiSource`] for texturing each cell with a tile from an atlas.
+/// The result's value is the uv of the sample within the nearest cell: the cell's point is at
+/// (0.5, 0.5), and a full grid cell away from it on either side is 0 or 1, clamped.
+/// The result's meta is the coordinate of the atlas tile for that cell, picked from its seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellUvAtlas {
+    /// The number of tiles in the atlas along each axis.
+    pub atlas_size: UVec2,
+}
+
+impl<const APPROX: bool> VoronoiSource<2, APPROX> for CellUvAtlas {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+impl CellUvAtlas {
+    /// Computes the uv and atlas tile of the nearest of these cells.
+    #[inline]
+    fn uv_of(&self, cells: &[Seeded<GridPoint2>]) -> Associated<Vec2, UVec2> {
+        let index = MinIndex(EuclideanDistance {
+            inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+        })
+        .merge(cells.iter().map(|cell| cell.value.offset), &());
+        let nearest = &cells[index];
+
+        let size = self.atlas_size.max(UVec2::ONE);
+        let tile = nearest.seed() % (size.x * size.y);
+        Associated {
+            value: (nearest.value.offset * 0.5 + 0.5).clamp(Vec2::ZERO, Vec2::ONE),
+            meta: UVec2::new(tile % size.x, tile / size.x),
+        }
+    }
+}
+
+impl NoiseOp<VoronoiGraph<Corners2d<Seeded<GridPoint2>>>> for CellUvAtlas {
+    type Output = Associated<Vec2, UVec2>;
+
+    #[inline]
+    fn get(&self, input: VoronoiGraph<Corners2d<Seeded<GridPoint2>>>) -> Self::Output {
+        self.uv_of(&input.value.0)
+    }
+}
+
+impl NoiseOp<VoronoiGraph<Surroundings2d<Seeded<GridPoint2>>>> for CellUvAtlas {
+    type Output = Associated<Vec2, UVec2>;
+
+    #[inline]
+    fn get(&self, input: VoronoiGraph<Surroundings2d<Seeded<GridPoint2>>>) -> Self::Output {
+        self.uv_of(&input.value.0)
+    }
+}
+
+/// A 2d [`VoronoiSource`] that fills each cell with a random two color gradient.
+/// Each cell gets two random colors and a random direction from its seed. The colors are blended
+/// linearly by the uv of the sample along that direction: the cell's point is halfway between
+/// them, and a full grid cell away from it is one color or the other.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CellGradient;
+
+impl<const APPROX: bool> VoronoiSource<2, APPROX> for CellGradient {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        Self
+    }
+}
+
+impl CellGradient {
+    /// Computes the color of the nearest of these cells.
+    #[inline]
+    fn color_of(&self, cells: &[Seeded<GridPoint2>]) -> Vec3 {
+        let index = MinIndex(EuclideanDistance {
+            inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+        })
+        .merge(cells.iter().map(|cell| cell.value.offset), &());
+        let nearest = &cells[index];
+
+        let random = |channel: u32| nearest.rng(channel).adapt::<UNorm>().adapt::<f32>();
+        let start = Vec3::new(random(0), random(1), random(2));
+        let end = Vec3::new(random(3), random(4), random(5));
+        let direction = Vec2::from_angle(random(6) * core::f32::consts::TAU);
+        let t = (nearest.value.offset.dot(direction) * 0.5 + 0.5).clamp(0.0, 1.0);
+        start.lerp(end, t)
+    }
+}
+
+impl NoiseOp<VoronoiGraph<Corners2d<Seeded<GridPoint2>>>> for CellGradient {
+    type Output = Vec3;
+
+    #[inline]
+    fn get(&self, input: VoronoiGraph<Corners2d<Seeded<GridPoint2>>>) -> Self::Output {
+        self.color_of(&input.value.0)
+    }
+}
+
+impl NoiseOp<VoronoiGraph<Surroundings2d<Seeded<GridPoint2>>>> for CellGradient {
+    type Output = Vec3;
+
+    #[inline]
+    fn get(&self, input: VoronoiGraph<Surroundings2d<Seeded<GridPoint2>>>) -> Self::Output {
+        self.color_of(&input.value.0)
+    }
+}
+
 /// A [`VoronoiSource`] that returns the relative distance of each point to the nearest edge.
+///
+/// The [`Orderer`] `O` is only used to pick the nearest two cells. The distance to the edge between
+/// them is always euclidean. Ranking by a different metric, like [`ChebyshevDistance`], is useful
+/// to match the cells of worley noise that uses that metric.
+#[derive(Debug, Clone, Copy)]
+pub struct RelativeDistanceToEdge<O = EuclideanDistance>(pub O);
+
+impl Default for RelativeDistanceToEdge {
+    fn default() -> Self {
+        Self(EuclideanDistance {
+            inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+        })
+    }
+}
+
+impl<const DIMENSIONS: u8, O> VoronoiSource<DIMENSIONS, false> for RelativeDistanceToEdge<O> {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] that returns the exact distance of each point to the nearest edge.
+///
+/// Like [`RelativeDistanceToEdge`], the [`Orderer`] `O` is only used to pick the nearest two cells.
+#[derive(Debug, Clone, Copy)]
+pub struct ExactDistanceToEdge<O = EuclideanDistance>(pub O);
+
+impl Default for ExactDistanceToEdge {
+    fn default() -> Self {
+        Self(EuclideanDistance {
+            inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+        })
+    }
+}
+
+impl<const DIMENSIONS: u8, O> VoronoiSource<DIMENSIONS, false> for ExactDistanceToEdge<O> {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] that returns the manhattan (L1) distance of each point to the nearest edge,
+/// which makes blocky borders.
+///
+/// Like [`ExactDistanceToEdge`], the edge is approximated as the perpendicular bisector between the
+/// nearest two cells, and the [`Orderer`] `O` is only used to pick them. The L1 distance to that
+/// plane is the signed distance along its normal divided by the largest component of the normal.
+/// The result is clamped to a [`UNorm`].
+#[derive(Debug, Clone, Copy)]
+pub struct ManhattanDistanceToEdge<O = EuclideanDistance>(pub O);
+
+impl Default for ManhattanDistanceToEdge {
+    fn default() -> Self {
+        Self(EuclideanDistance {
+            inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+        })
+    }
+}
+
+impl<const DIMENSIONS: u8, O> VoronoiSource<DIMENSIONS, false> for ManhattanDistanceToEdge<O> {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] for the mortar between bricks or tiles.
+/// The result is at its maximum within [`width`](Self::width) of the edge between the nearest two
+/// cells, plus that cell's inset, and 0.0 elsewhere. The distance to the edge is that of
+/// [`ExactDistanceToEdge`], so the width is in units of grid cells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mortar {
+    /// How far from each edge the mortar reaches.
+    pub width: f32,
+    /// The most each cell is inset by, as a fraction of [`width`](Self::width). Each cell picks
+    /// its own inset from 0 up to this based on its seed, which widens the mortar around it.
+    pub inset: f32,
+}
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for Mortar {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] that gives each cell a constant random value, smoothly blending between
+/// neighboring cells' values within a band `blend` wide around their shared edge.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SoftRegionValue {
+    /// The width of the band around each edge where values blend.
+    pub blend: f32,
+}
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for SoftRegionValue {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] that returns a random value for the edge between the nearest two cells.
+/// Every sample along the same wall gets the same value, regardless of which side it is on.
 #[derive(Debug, Clone, Copy, Default)]
-pub struct RelativeDistanceToEdge;
+pub struct EdgeRandom;
 
-impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for RelativeDistanceToEdge {
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for EdgeRandom {
     type Noise = Self;
 
     fn build_noise(self, _max_nudge: f32) -> Self::Noise {
@@ -337,11 +770,53 @@ impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for RelativeDistance
     }
 }
 
-/// A [`VoronoiSource`] that returns the exact distance of each point to the nearest edge.
+/// A [`VoronoiSource`] for blobs of different sizes, like craters or spots.
+/// Each cell's point gets a random radius between `min_radius` and `max_radius`, in units of grid
+/// cells. The result is the smallest distance to a point divided by its radius, so samples inside
+/// a blob are less than 1.0, with 0.0 at the blob's center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VariableRadiusWorley {
+    /// The smallest radius a blob can have.
+    pub min_radius: f32,
+    /// The largest radius a blob can have.
+    pub max_radius: f32,
+}
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for VariableRadiusWorley {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] for smooth voronoi, an organic cellular noise that, unlike the plain nearest
+/// distance, is continuous and has no creases along cell walls.
+/// The result is the average distance to the points, in units of grid cells, weighted by
+/// `exp(-falloff * distance)`. The larger the [`falloff`](Self::falloff), the closer this gets to
+/// the distance to the nearest point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothVoronoi {
+    /// How quickly farther points lose their influence.
+    pub falloff: f32,
+}
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for SmoothVoronoi {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
+
+/// A [`VoronoiSource`] that estimates the area (or volume) of the nearest cell.
+/// This is only an estimate: it is the mean distance from the nearest cell's point to its closest
+/// neighbors (one per dimension), raised to the number of dimensions. That is exact for an unnudged
+/// grid, and only the neighbors within the sampled surroundings are considered.
 #[derive(Debug, Clone, Copy, Default)]
-pub struct ExactDistanceToEdge;
+pub struct CellAreaEstimate;
 
-impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for ExactDistanceToEdge {
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for CellAreaEstimate {
     type Noise = Self;
 
     fn build_noise(self, _max_nudge: f32) -> Self::Noise {
@@ -349,19 +824,65 @@ impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for ExactDistanceToE
     }
 }
 
-/// easily implements worly for different inputs
-macro_rules! impl_voronoi {
-    ($point:path, $vec:path, $d:literal, $d_2:ident, $d_3:ident) => {
-        // worly
+/// A [`VoronoiSource`] that finds the cells next to the nearest cell, which is useful for
+/// connecting regions, like rooms in a dungeon. The result has the seed of each surrounding cell
+/// that is a neighbor, and [`None`] for the rest, including the nearest cell itself.
+///
+/// Two cells are neighbors if the midpoint between their points is on their shared edge, that is,
+/// no other point is as close to it. Cells that only meet at a corner are not neighbors. This
+/// misses neighbors that only touch along a short edge away from that midpoint, and only the cells
+/// within the sampled surroundings are considered.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CellNeighbors;
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for CellNeighbors {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        Self
+    }
+}
+
+/// A [`VoronoiSource`] for overlapping scales, like those of fish or reptiles.
+/// Each cell's point is the center of a round scale with a radius of `1 / curvature` grid cells.
+/// Where scales overlap, the one whose point is lowest along the y axis is on top, so each scale
+/// is cut off by an arc from the scales below it. The result is the distance to the center of the
+/// visible scale divided by its radius, or 1.0 if no scale covers the sample.
+///
+/// The [`curvature`](Self::curvature) should be at most 1.0 so the scales cover every cell, and
+/// not much less, since only the points within the sampled surroundings are considered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scales {
+    /// How tightly the edges of the scales curve.
+    pub curvature: f32,
+}
+
+impl Default for Scales {
+    fn default() -> Self {
+        Self { curvature: 1.0 }
+    }
+}
+
+impl<const DIMENSIONS: u8> VoronoiSource<DIMENSIONS, false> for Scales {
+    type Noise = Self;
+
+    fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+        self
+    }
+}
 
-        impl<S: VoronoiSource<$d, true>> NoiseOp<$point> for Voronoi<$d, S, true>
+/// easily implements [`Voronoi`] for different inputs, which must have `corners` and
+/// `surroundings` of grid points.
+macro_rules! impl_voronoi_for {
+    ($input:ty, $point:path, $d:literal, $d_2:ident, $d_3:ident) => {
+        impl<S: VoronoiSource<$d, true>> NoiseOp<$input> for Voronoi<$d, S, true>
         where
             S::Noise: NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>>,
         {
             type Output = <S::Noise as NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>>>::Output;
 
             #[inline]
-            fn get(&self, input: $point) -> Self::Output {
+            fn get(&self, input: $input) -> Self::Output {
                 let points = input.corners().map(|point| {
                     let mut seeded = self.seeder.get(point);
                     let grid_shift = self.nudge.get(seeded.map_ref(|p| p.base)).value;
@@ -376,14 +897,14 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl<S: VoronoiSource<$d, false>> NoiseOp<$point> for Voronoi<$d, S, false>
+        impl<S: VoronoiSource<$d, false>> NoiseOp<$input> for Voronoi<$d, S, false>
         where
             S::Noise: NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>>,
         {
             type Output = <S::Noise as NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>>>::Output;
 
             #[inline]
-            fn get(&self, input: $point) -> Self::Output {
+            fn get(&self, input: $input) -> Self::Output {
                 let points = input.surroundings().map(|point| {
                     let mut seeded = self.seeder.get(point);
                     let grid_shift = self.nudge.get(seeded.map_ref(|p| p.base)).value;
@@ -397,19 +918,55 @@ macro_rules! impl_voronoi {
                 self.source.get(voronoi)
             }
         }
+    };
+}
+
+/// easily implements worley for different inputs
+macro_rules! impl_voronoi {
+    ($point:path, $vec:path, $uvec:ty, $d:literal, $d_2:ident, $d_3:ident) => {
+        // worley
+
+        impl_voronoi_for!($point, $point, $d, $d_2, $d_3);
+        impl_voronoi_for!(WrappingGridPoint<$point, $uvec>, $point, $d, $d_2, $d_3);
+
+        impl VoronoiGraph<$d_2<Seeded<$point>>> {
+            /// Recovers the grid point that was sampled to produce this graph.
+            #[inline]
+            pub fn sampled_point(&self) -> $point {
+                // the first corner is the cell that was sampled.
+                let cell = &self.value.0[0];
+                let shift = self.meta.get(cell.map_ref(|p| p.base)).value;
+                $point {
+                    base: cell.value.base,
+                    offset: cell.value.offset + shift,
+                }
+            }
+        }
+
+        impl VoronoiGraph<$d_3<Seeded<$point>>> {
+            /// Recovers the grid point that was sampled to produce this graph.
+            #[inline]
+            pub fn sampled_point(&self) -> $point {
+                // the last surrounding is the cell that was sampled.
+                let cell = &self.value.0[self.value.0.len() - 1];
+                let shift = self.meta.get(cell.map_ref(|p| p.base)).value;
+                $point {
+                    base: cell.value.base,
+                    offset: cell.value.offset + shift,
+                }
+            }
+        }
 
         // distance to edge
 
-        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for RelativeDistanceToEdge {
+        impl<O: Orderer<$vec>> NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for RelativeDistanceToEdge<O> {
             type Output = UNorm;
 
             #[inline]
             fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
                 let points = input.value.map(|point| point.value.offset);
-                let [nearest, next] = $crate::noise::merging::MinIndices(EuclideanDistance {
-                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
-                })
-                .merge(points.0.iter().copied(), &())
+                let [nearest, next] = $crate::noise::merging::MinIndices(&self.0)
+                    .merge(points.0.iter().copied(), &())
                 .map(|i| points.0[i]);
 
                 let boarder_to_nearest = (next - nearest) * 0.5;
@@ -420,16 +977,14 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for ExactDistanceToEdge {
+        impl<O: Orderer<$vec>> NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for ExactDistanceToEdge<O> {
             type Output = f32;
 
             #[inline]
             fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
                 let points = input.value.map(|point| point.value.offset);
-                let [nearest, next] = $crate::noise::merging::MinIndices(EuclideanDistance {
-                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
-                })
-                .merge(points.0.iter().copied(), &())
+                let [nearest, next] = $crate::noise::merging::MinIndices(&self.0)
+                    .merge(points.0.iter().copied(), &())
                 .map(|i| points.0[i]);
 
                 let boarder_to_nearest = (next - nearest) * 0.5;
@@ -438,34 +993,247 @@ macro_rules! impl_voronoi {
             }
         }
 
-        // worly
+        impl<O: Orderer<$vec>> NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for ManhattanDistanceToEdge<O> {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.map(|point| point.value.offset);
+                let [nearest, next] = $crate::noise::merging::MinIndices(&self.0)
+                    .merge(points.0.iter().copied(), &())
+                .map(|i| points.0[i]);
+
+                let boarder_to_nearest = (next - nearest) * 0.5;
+                let boarder_to_sample = boarder_to_nearest + nearest;
+                let result = boarder_to_sample.dot(boarder_to_nearest)
+                    / boarder_to_nearest.abs().max_element();
+                UNorm::new_clamped(result)
+            }
+        }
+
+        // mortar
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for Mortar {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let distance = ExactDistanceToEdge::default().get(input.clone());
+                let inset = CellValue.get(input).adapt::<f32>() * self.inset;
+                let width = self.width * (1.0 + inset);
+                UNorm::new_clamped(if distance < width { 1.0 } else { 0.0 })
+            }
+        }
+
+        // soft regions
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for SoftRegionValue {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.clone().map(|point| point.value.offset);
+                let [nearest, next] = $crate::noise::merging::MinIndices(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points.0.iter().copied(), &());
+                let value_of = |index: usize| input.value.0[index].rng(0).adapt::<UNorm>().adapt::<f32>();
+                let inside = value_of(nearest);
+
+                let half_blend = self.blend * 0.5;
+                let boarder_to_nearest = (points.0[next] - points.0[nearest]) * 0.5;
+                let boarder_to_sample = boarder_to_nearest + points.0[nearest];
+                let distance =
+                    boarder_to_sample.dot(boarder_to_nearest) / boarder_to_nearest.length();
+                if distance >= half_blend {
+                    return UNorm::new_clamped(inside);
+                }
+
+                let t = (distance / half_blend).max(0.0);
+                let weight = 0.5 + 0.5 * t * t * (3.0 - 2.0 * t);
+                let outside = value_of(next);
+                UNorm::new_clamped(outside + (inside - outside) * weight)
+            }
+        }
+
+        // edge random
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for EdgeRandom {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.clone().map(|point| point.value.offset);
+                let [nearest, next] = $crate::noise::merging::MinIndices(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points.0, &())
+                .map(|i| input.value.0[i].seed());
+
+                // sort the seeds so both sides of the wall agree.
+                let (low, high) = if nearest < next {
+                    (nearest, next)
+                } else {
+                    (next, nearest)
+                };
+                White32(low).get(high).adapt()
+            }
+        }
+
+        // variable radius
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for VariableRadiusWorley {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let closest = input.value.0.iter().fold(f32::INFINITY, |closest, point| {
+                    let t = point.rng(1).adapt::<UNorm>().adapt::<f32>();
+                    let radius = self.min_radius + (self.max_radius - self.min_radius) * t;
+                    closest.min(point.value.offset.length() / radius)
+                });
+                UNorm::new_clamped(closest)
+            }
+        }
+
+        // smooth voronoi
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for SmoothVoronoi {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let distances = input.value.map(|point| point.value.offset.length());
+                // weights are relative to the nearest point so they can't all underflow to 0.
+                let nearest = distances.0.iter().copied().fold(f32::INFINITY, f32::min);
+                let mut total = 0.0;
+                let mut total_weight = 0.0;
+                for distance in distances.0 {
+                    let weight = (-self.falloff * (distance - nearest)).exp();
+                    total += distance * weight;
+                    total_weight += weight;
+                }
+                UNorm::new_clamped(total / total_weight)
+            }
+        }
+
+        // cell area
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for CellAreaEstimate {
+            type Output = f32;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.map(|point| point.value.offset);
+                let nearest = MinIndex(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points.0.iter().copied(), &());
+
+                // keep one neighbor per axis, since the nearest point may be on the edge of the
+                // surroundings, where only one neighbor per axis is known.
+                let mut closest = [f32::INFINITY; $d];
+                for (index, point) in points.0.iter().enumerate() {
+                    if index == nearest {
+                        continue;
+                    }
+                    let mut dist = point.distance(points.0[nearest]);
+                    for slot in closest.iter_mut() {
+                        if dist < *slot {
+                            core::mem::swap(&mut dist, slot);
+                        }
+                    }
+                }
+
+                let mean = closest.iter().sum::<f32>() / closest.len() as f32;
+                mean.powi($d)
+            }
+        }
+
+        // cell neighbors
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for CellNeighbors {
+            type Output = $d_3<Option<u32>>;
 
-        impl<O: Orderer<$vec, OrderingOutput = UNorm>, M: WorlyMode>
-            NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>> for WorlyNoise<O, M>
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.clone().map(|point| point.value.offset);
+                let nearest = MinIndex(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points.0.iter().copied(), &());
+                let home = points.0[nearest];
+
+                let mut neighbors = input.value.map(|point| Some(point.seed()));
+                for (index, neighbor) in neighbors.0.iter_mut().enumerate() {
+                    let midpoint = (home + points.0[index]) * 0.5;
+                    let radius = midpoint.distance_squared(home);
+                    let blocked = points.0.iter().enumerate().any(|(other, point)| {
+                        other != nearest
+                            && other != index
+                            && point.distance_squared(midpoint) <= radius
+                    });
+                    if index == nearest || blocked {
+                        *neighbor = None;
+                    }
+                }
+                neighbors
+            }
+        }
+
+        // scales
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for Scales {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let radius = 1.0 / make_nonzero_f32(self.curvature);
+                // the offset is from the point to the sample, so the lowest point has the largest y.
+                let mut top: Option<(f32, f32)> = None;
+                for point in input.value.0.iter() {
+                    let offset = point.value.offset;
+                    let distance = offset.length();
+                    if distance >= radius {
+                        continue;
+                    }
+                    let key = (offset.y, -distance);
+                    if top.is_none_or(|(y, nearness)| key > (y, nearness)) {
+                        top = Some(key);
+                    }
+                }
+                UNorm::new_clamped(top.map_or(1.0, |(_, nearness)| -nearness / radius))
+            }
+        }
+
+        // worley
+
+        impl<O: Orderer<$vec, OrderingOutput = UNorm>, M: WorleyMode>
+            NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>> for WorleyNoise<O, M>
         {
             type Output = UNorm;
 
             #[inline]
             fn get(&self, input: VoronoiGraph<$d_2<Seeded<$point>>>) -> Self::Output {
                 let points = input.value.map(|point| point.value.offset);
-                self.1.compute_worly(&self.0, points.0)
+                self.1.compute_worley(&self.0, points.0)
             }
         }
 
-        impl<O: Orderer<$vec, OrderingOutput = UNorm>, M: WorlyMode>
-            NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for WorlyNoise<O, M>
+        impl<O: Orderer<$vec, OrderingOutput = UNorm>, M: WorleyMode>
+            NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for WorleyNoise<O, M>
         {
             type Output = UNorm;
 
             #[inline]
             fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
                 let points = input.value.map(|point| point.value.offset);
-                self.1.compute_worly(&self.0, points.0)
+                self.1.compute_worley(&self.0, points.0)
             }
         }
 
-        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worly<EuclideanDistance, M> {
-            type Noise = WorlyNoise<EuclideanDistance, M>;
+        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worley<EuclideanDistance, M> {
+            type Noise = WorleyNoise<EuclideanDistance, M>;
 
             fn build_noise(self, max_nudge: f32) -> Self::Noise {
                 let max_displacement = max_nudge * self.expected_length_multiplier;
@@ -475,7 +1243,7 @@ macro_rules! impl_voronoi {
                 } else {
                     (max_displacement * max_displacement * ($d as f32)).sqrt()
                 };
-                WorlyNoise(
+                WorleyNoise(
                     EuclideanDistance {
                         inv_max_expected: 1.0 / max_dist,
                     },
@@ -484,8 +1252,8 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worly<ManhatanDistance, M> {
-            type Noise = WorlyNoise<ManhatanDistance, M>;
+        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worley<ManhattanDistance, M> {
+            type Noise = WorleyNoise<ManhattanDistance, M>;
 
             fn build_noise(self, max_nudge: f32) -> Self::Noise {
                 let max_displacement = max_nudge * self.expected_length_multiplier;
@@ -495,8 +1263,8 @@ macro_rules! impl_voronoi {
                 } else {
                     max_displacement * ($d as f32)
                 };
-                WorlyNoise(
-                    ManhatanDistance {
+                WorleyNoise(
+                    ManhattanDistance {
                         inv_max_expected: 1.0 / max_dist,
                     },
                     self.mode,
@@ -504,8 +1272,8 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worly<HybridDistance, M> {
-            type Noise = WorlyNoise<HybridDistance, M>;
+        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worley<HybridDistance, M> {
+            type Noise = WorleyNoise<HybridDistance, M>;
 
             fn build_noise(self, max_nudge: f32) -> Self::Noise {
                 let max_displacement = max_nudge * self.expected_length_multiplier;
@@ -515,7 +1283,7 @@ macro_rules! impl_voronoi {
                 } else {
                     (max_displacement * max_displacement + max_displacement) * ($d as f32)
                 };
-                WorlyNoise(
+                WorleyNoise(
                     HybridDistance {
                         inv_max_expected: 1.0 / max_dist,
                     },
@@ -524,8 +1292,30 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worly<ChebyshevDistance, M> {
-            type Noise = WorlyNoise<ChebyshevDistance, M>;
+        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for MinkowskiWorley<M> {
+            type Noise = WorleyNoise<MinkowskiDistance, M>;
+
+            fn build_noise(self, max_nudge: f32) -> Self::Noise {
+                let max_displacement = max_nudge * self.expected_length_multiplier;
+                let max_dist = if APPROX {
+                    // a negative cell could be at the same spot on all axies but the cell's offset.
+                    max_displacement
+                } else {
+                    // the norm of a vector with every element at the max displacement.
+                    max_displacement * ($d as f32).powf(1.0 / self.p)
+                };
+                WorleyNoise(
+                    MinkowskiDistance {
+                        p: self.p,
+                        inv_max_expected: 1.0 / max_dist,
+                    },
+                    self.mode,
+                )
+            }
+        }
+
+        impl<const APPROX: bool, M> VoronoiSource<$d, APPROX> for Worley<ChebyshevDistance, M> {
+            type Noise = WorleyNoise<ChebyshevDistance, M>;
 
             fn build_noise(self, max_nudge: f32) -> Self::Noise {
                 let max_displacement = max_nudge * self.expected_length_multiplier;
@@ -537,7 +1327,7 @@ macro_rules! impl_voronoi {
                     // the center can get from the suroundings.
                     max_displacement * 1.5
                 };
-                WorlyNoise(
+                WorleyNoise(
                     ChebyshevDistance {
                         inv_max_expected: 1.0 / max_dist,
                     },
@@ -546,6 +1336,93 @@ macro_rules! impl_voronoi {
             }
         }
 
+        impl VoronoiSource<$d, false> for WorleyF1F4 {
+            type Noise = WorleyF1F4Noise;
+
+            fn build_noise(self, max_nudge: f32) -> Self::Noise {
+                WorleyF1F4Noise(
+                    <Worley<EuclideanDistance, worley_mode::Nearest> as VoronoiSource<$d, false>>::build_noise(
+                        Worley::new_expanded_by(self.expected_length_multiplier, worley_mode::Nearest),
+                        max_nudge,
+                    )
+                    .0,
+                )
+            }
+        }
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for WorleyF1F4Noise {
+            type Output = Vec4;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.map(|point| point.value.offset);
+                let features: [UNorm; 4] = MinOrdersN(self.0).merge(points.0, &());
+                Vec4::from_array(features.map(|v| v.adapt::<f32>()))
+            }
+        }
+
+        impl<const APPROX: bool> VoronoiSource<$d, APPROX> for Bubbles {
+            type Noise = BubblesNoise;
+
+            fn build_noise(self, max_nudge: f32) -> Self::Noise {
+                BubblesNoise {
+                    nearest: <Worley<EuclideanDistance, worley_mode::Nearest> as VoronoiSource<
+                        $d,
+                        APPROX,
+                    >>::build_noise(Worley::default(), max_nudge),
+                    falloff: self.falloff,
+                }
+            }
+        }
+
+        impl<const APPROX: bool, C, M: Clone> VoronoiSource<$d, APPROX> for BlendedWorley<C, M> {
+            type Noise = BlendedWorleyNoise<C, M>;
+
+            fn build_noise(self, max_nudge: f32) -> Self::Noise {
+                BlendedWorleyNoise {
+                    euclidean:
+                        <Worley<EuclideanDistance, M> as VoronoiSource<$d, APPROX>>::build_noise(
+                            Worley::new_shrunk_by(1.0, self.mode.clone()),
+                            max_nudge,
+                        ),
+                    manhattan:
+                        <Worley<ManhattanDistance, M> as VoronoiSource<$d, APPROX>>::build_noise(
+                            Worley::new_shrunk_by(1.0, self.mode),
+                            max_nudge,
+                        ),
+                    control: self.control,
+                }
+            }
+        }
+
+        impl<C: NoiseOp<$point, Output = UNorm>, M: WorleyMode>
+            NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>> for BlendedWorleyNoise<C, M>
+        {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_2<Seeded<$point>>>) -> Self::Output {
+                let blend = self.control.get(input.sampled_point()).adapt::<f32>();
+                let euclidean = self.euclidean.get(input.clone()).adapt::<f32>();
+                let manhattan = self.manhattan.get(input).adapt::<f32>();
+                UNorm::new_clamped(euclidean + (manhattan - euclidean) * blend)
+            }
+        }
+
+        impl<C: NoiseOp<$point, Output = UNorm>, M: WorleyMode>
+            NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for BlendedWorleyNoise<C, M>
+        {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let blend = self.control.get(input.sampled_point()).adapt::<f32>();
+                let euclidean = self.euclidean.get(input.clone()).adapt::<f32>();
+                let manhattan = self.manhattan.get(input).adapt::<f32>();
+                UNorm::new_clamped(euclidean + (manhattan - euclidean) * blend)
+            }
+        }
+
         // cellular
 
         // we can't generalize CellularNoise's array length since length of 0 is unsafe.
@@ -575,6 +1452,34 @@ macro_rules! impl_voronoi {
             }
         }
 
+        impl NoiseOp<VoronoiGraph<$d_2<Seeded<$point>>>> for CellValue {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_2<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.clone().map(|point| point.value.offset).0;
+                let index = MinIndex(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points, &());
+                input.value.0[index].rng(0).adapt()
+            }
+        }
+
+        impl NoiseOp<VoronoiGraph<$d_3<Seeded<$point>>>> for CellValue {
+            type Output = UNorm;
+
+            #[inline]
+            fn get(&self, input: VoronoiGraph<$d_3<Seeded<$point>>>) -> Self::Output {
+                let points = input.value.clone().map(|point| point.value.offset).0;
+                let index = MinIndex(EuclideanDistance {
+                    inv_max_expected: 1.0, // doesn't matter since we are just comparing them.
+                })
+                .merge(points, &());
+                input.value.0[index].rng(0).adapt()
+            }
+        }
+
         impl<const APPROX: bool> VoronoiSource<$d, APPROX> for Cellular<EuclideanDistance> {
             type Noise = CellularNoise<EuclideanDistance>;
 
@@ -585,11 +1490,11 @@ macro_rules! impl_voronoi {
             }
         }
 
-        impl<const APPROX: bool> VoronoiSource<$d, APPROX> for Cellular<ManhatanDistance> {
-            type Noise = CellularNoise<ManhatanDistance>;
+        impl<const APPROX: bool> VoronoiSource<$d, APPROX> for Cellular<ManhattanDistance> {
+            type Noise = CellularNoise<ManhattanDistance>;
 
             fn build_noise(self, _max_nudge: f32) -> Self::Noise {
-                CellularNoise(ManhatanDistance {
+                CellularNoise(ManhattanDistance {
                     inv_max_expected: 0.0,
                 })
             }
@@ -604,9 +1509,624 @@ macro_rules! impl_voronoi {
                 })
             }
         }
+
+        impl<const APPROX: bool> VoronoiSource<$d, APPROX> for Cellular<ChebyshevDistance> {
+            type Noise = CellularNoise<ChebyshevDistance>;
+
+            fn build_noise(self, _max_nudge: f32) -> Self::Noise {
+                CellularNoise(ChebyshevDistance {
+                    inv_max_expected: 0.0,
+                })
+            }
+        }
     };
 }
 
-impl_voronoi!(GridPoint2, Vec2, 2, Corners2d, Surroundings2d);
-impl_voronoi!(GridPoint3, Vec3, 3, Corners3d, Surroundings3d);
-impl_voronoi!(GridPoint4, Vec4, 4, Corners4d, Surroundings4d);
+impl_voronoi!(GridPoint2, Vec2, UVec2, 2, Corners2d, Surroundings2d);
+impl_voronoi!(GridPoint3, Vec3, UVec3, 3, Corners3d, Surroundings3d);
+impl_voronoi!(GridPoint4, Vec4, UVec4, 4, Corners4d, Surroundings4d);
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::*;
+    use crate::noise::grid::{
+        GridNoise,
+        WrappingGridNoise,
+    };
+
+    /// Finds where the point of the cell containing `cell` was nudged to for a [`Voronoi`] of
+    /// this `range` and `seed`.
+    fn cell_center(grid: &GridNoise, range: f32, seed: u32, cell: Vec2) -> Vec2 {
+        let base = grid.get(cell).base;
+        let shift = Nudge::<true>::new_magnitude(range).get(Seeding(seed).get(base));
+        cell.floor() + shift.value
+    }
+
+    #[test]
+    fn edge_random_is_stable_per_edge() {
+        // with no nudge, cells are centered on integer points, so x = 0.5 is a wall.
+        let grid = GridNoise::new_frequency(1.0);
+        let voronoi = Voronoi::<2, EdgeRandom>::new(0.0, 42, EdgeRandom);
+        let sample = |x: f32, y: f32| voronoi.get(grid.get(Vec2::new(x, y)));
+
+        let wall = sample(0.45, 0.1);
+        assert_eq!(wall, sample(0.55, 0.1));
+        assert_eq!(wall, sample(0.48, -0.3));
+        assert_eq!(wall, sample(0.6, 0.2));
+
+        let others = [
+            sample(0.1, 0.45),
+            sample(-0.45, 0.1),
+            sample(0.1, -0.45),
+            sample(1.55, 0.1),
+            sample(2.1, 3.45),
+        ];
+        assert!(others.iter().any(|other| *other != wall));
+    }
+
+    #[test]
+    fn bubbles_are_low_in_centers_and_high_on_edges() {
+        let grid = GridNoise::new_frequency(1.0);
+        let voronoi = Voronoi::<2, Bubbles>::new(0.5, 7, Bubbles { falloff: 8.0 });
+        let sample = |p: Vec2| voronoi.get(grid.get(p)).adapt::<f32>();
+
+        for cell in [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(3.0, -2.0),
+            Vec2::new(-5.0, 4.0),
+        ] {
+            let center = cell_center(&grid, 0.5, 7, cell);
+            let neighbor = cell_center(&grid, 0.5, 7, cell + Vec2::X);
+            let edge = (center + neighbor) * 0.5;
+            assert!(sample(center) < 0.01);
+            assert!(sample(edge) > 0.4);
+        }
+    }
+
+    struct Constant(UNorm);
+
+    impl NoiseOp<GridPoint2> for Constant {
+        type Output = UNorm;
+
+        fn get(&self, _input: GridPoint2) -> Self::Output {
+            self.0
+        }
+    }
+
+    #[test]
+    fn blended_worley_matches_pure_metrics() {
+        let grid = GridNoise::new_frequency(1.0);
+        let euclidean =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>>::new_default(1.0, 3);
+        let manhattan =
+            Voronoi::<2, Worley<ManhattanDistance, worley_mode::Nearest>>::new_default(1.0, 3);
+        let all_euclidean = Voronoi::<2, BlendedWorley<Constant>>::new(
+            1.0,
+            3,
+            BlendedWorley {
+                control: Constant(UNorm::new_clamped(0.0)),
+                mode: worley_mode::Nearest,
+            },
+        );
+        let all_manhattan = Voronoi::<2, BlendedWorley<Constant>>::new(
+            1.0,
+            3,
+            BlendedWorley {
+                control: Constant(UNorm::new_clamped(1.0)),
+                mode: worley_mode::Nearest,
+            },
+        );
+
+        for i in 0..50 {
+            let point = grid.get(Vec2::new(i as f32 * 0.37 - 9.0, i as f32 * -0.21 + 4.0));
+            let expected = euclidean.get(point.clone()).adapt::<f32>();
+            let blended = all_euclidean.get(point.clone()).adapt::<f32>();
+            assert!((expected - blended).abs() < 1e-5);
+            let expected = manhattan.get(point.clone()).adapt::<f32>();
+            let blended = all_manhattan.get(point).adapt::<f32>();
+            assert!((expected - blended).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn sampled_point_is_recovered() {
+        let grid = GridNoise::new_frequency(1.0);
+        let seeder = Seeding(11);
+        let nudge = Nudge::<true>::new_magnitude(0.8);
+        let point = grid.get(Vec2::new(-3.3, 7.8));
+        let graph = VoronoiGraph {
+            value: point.surroundings().map(|p| {
+                let mut seeded = seeder.get(p);
+                seeded.value.offset -= nudge.get(seeded.map_ref(|p| p.base)).value;
+                seeded
+            }),
+            meta: nudge,
+        };
+        let recovered = graph.sampled_point();
+        assert_eq!(recovered.base, point.base);
+        assert!((recovered.offset - point.offset).length() < 1e-5);
+    }
+
+    fn worley_histogram(period: f32, start: Vec2) -> [f32; 10] {
+        let grid = GridNoise::new_period(period);
+        let worley =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>>::new_default(1.0, 21);
+        let mut histogram = [0.0; 10];
+        let samples = 100;
+        let step = period * 40.0 / samples as f32;
+        for y in 0..samples {
+            for x in 0..samples {
+                let point = start + Vec2::new(x as f32, y as f32) * step;
+                let value = worley.get(grid.get(point)).adapt::<f32>();
+                histogram[((value * 10.0) as usize).min(9)] += 1.0 / (samples * samples) as f32;
+            }
+        }
+        histogram
+    }
+
+    #[test]
+    fn worley_is_scale_invariant() {
+        let small = worley_histogram(4.0, Vec2::new(13.0, -7.0));
+        let large = worley_histogram(57.0, Vec2::new(-2000.0, 900.0));
+        for (small, large) in small.into_iter().zip(large) {
+            assert!((small - large).abs() < 0.03);
+        }
+    }
+
+    fn mean_cell_area(range: f32) -> f32 {
+        let grid = GridNoise::new_period(1.0);
+        let voronoi = Voronoi::<2, CellAreaEstimate>::new_default(range, 5);
+        let mut total = 0.0;
+        for y in 0..50 {
+            for x in 0..50 {
+                total += voronoi.get(grid.get(Vec2::new(x as f32, y as f32) * 0.7 + 0.1));
+            }
+        }
+        total / 2500.0
+    }
+
+    #[test]
+    fn nudged_cells_are_estimated_smaller() {
+        let unnudged = mean_cell_area(0.0);
+        assert!((unnudged - 1.0).abs() < 1e-4);
+        let nudged = mean_cell_area(1.0);
+        assert!(nudged < unnudged);
+    }
+
+    #[test]
+    fn worley_features_are_ordered() {
+        let grid = GridNoise::new_period(3.0);
+        let voronoi = Voronoi::<2, WorleyF1F4>::new(
+            1.0,
+            17,
+            WorleyF1F4 {
+                expected_length_multiplier: 3.0,
+            },
+        );
+        for y in -15..15 {
+            for x in -15..15 {
+                let features = voronoi.get(grid.get(Vec2::new(x as f32, y as f32) * 0.9));
+                assert!(features.x <= features.y);
+                assert!(features.y <= features.z);
+                assert!(features.z <= features.w);
+            }
+        }
+    }
+
+    #[test]
+    fn soft_regions_blend_at_borders() {
+        // with no nudge, cells are centered on integer points, so x = 0.5 is a wall.
+        let grid = GridNoise::new_frequency(1.0);
+        let soft = Voronoi::<2, SoftRegionValue>::new(0.0, 11, SoftRegionValue { blend: 0.4 });
+        let cellular = Voronoi::<2, Cellular<EuclideanDistance>>::new_default(0.0, 11);
+        let constant = |p: Vec2| {
+            cellular
+                .get(grid.get(p))
+                .rng(0)
+                .adapt::<UNorm>()
+                .adapt::<f32>()
+        };
+        let sample = |p: Vec2| soft.get(grid.get(p)).adapt::<f32>();
+
+        let left = Vec2::new(0.05, 0.1);
+        let right = Vec2::new(0.95, 0.1);
+        assert_eq!(sample(left), constant(left));
+        assert_eq!(sample(right), constant(right));
+
+        let border = sample(Vec2::new(0.52, 0.1));
+        let (low, high) = if constant(left) < constant(right) {
+            (constant(left), constant(right))
+        } else {
+            (constant(right), constant(left))
+        };
+        assert!(low < border && border < high);
+    }
+
+    #[test]
+    fn cell_values_are_constant_per_cell() {
+        // with no nudge, cells are centered on integer points, so x = 0.5 is a wall.
+        let grid = GridNoise::new_frequency(1.0);
+        let exact = Voronoi::<2, CellValue>::new_default(0.0, 5);
+        let approx = Voronoi::<2, CellValue, true>::new_default(0.0, 5);
+        let sample = |p: Vec2| {
+            let value = exact.get(grid.get(p)).adapt::<f32>();
+            assert_eq!(value, approx.get(grid.get(p)).adapt::<f32>());
+            value
+        };
+        assert_eq!(sample(Vec2::new(0.1, 0.2)), sample(Vec2::new(0.4, -0.3)));
+        assert_ne!(sample(Vec2::new(0.4, 0.2)), sample(Vec2::new(0.6, 0.2)));
+
+        let cube = Voronoi::<3, CellValue>::new_default(0.0, 5);
+        assert_eq!(
+            cube.get(grid.get(Vec3::new(2.1, 0.2, 0.0))),
+            cube.get(grid.get(Vec3::new(1.8, -0.2, 0.3)))
+        );
+    }
+
+    #[test]
+    fn edge_distance_ranking_is_swappable() {
+        let grid = GridNoise::new_period(2.0);
+        let euclidean = Voronoi::<2, RelativeDistanceToEdge>::new_default(1.0, 9);
+        let chebyshev = Voronoi::<2, RelativeDistanceToEdge<ChebyshevDistance>>::new(
+            1.0,
+            9,
+            RelativeDistanceToEdge(ChebyshevDistance {
+                inv_max_expected: 1.0,
+            }),
+        );
+        let manhattan = Voronoi::<2, ExactDistanceToEdge<ManhattanDistance>>::new(
+            1.0,
+            9,
+            ExactDistanceToEdge(ManhattanDistance {
+                inv_max_expected: 1.0,
+            }),
+        );
+        for y in -15..15 {
+            for x in -15..15 {
+                let point = grid.get(Vec2::new(x as f32, y as f32) * 0.6);
+                for value in [euclidean.get(point.clone()), chebyshev.get(point.clone())] {
+                    let value = value.adapt::<f32>();
+                    assert!((0.0..=1.0).contains(&value));
+                }
+                assert!(manhattan.get(point).is_finite());
+            }
+        }
+    }
+
+    #[test]
+    fn manhattan_edge_distance_is_zero_on_walls() {
+        // with no nudge, cells are centered on integer points, so x = 0.5 is a wall.
+        let grid = GridNoise::new_frequency(1.0);
+        let square = Voronoi::<2, ManhattanDistanceToEdge>::new_default(0.0, 4);
+        let distance = |x: f32, y: f32| square.get(grid.get(Vec2::new(x, y))).adapt::<f32>();
+        assert!(distance(0.5, 0.1) < 1e-5);
+        assert!((distance(0.3, 0.1) - 0.2).abs() < 1e-5);
+
+        let voronoi = Voronoi::<2, ManhattanDistanceToEdge>::new_default(1.0, 4);
+        for y in -15..15 {
+            for x in -15..15 {
+                let value = voronoi
+                    .get(grid.get(Vec2::new(x as f32, y as f32) * 0.37))
+                    .adapt::<f32>();
+                assert!((0.0..=1.0).contains(&value));
+            }
+        }
+    }
+
+    #[test]
+    fn variable_radius_blobs_grow() {
+        let grid = GridNoise::new_frequency(1.0);
+        let coverage = |min_radius: f32, max_radius: f32| {
+            let blobs = Voronoi::<2, VariableRadiusWorley>::new(
+                1.0,
+                3,
+                VariableRadiusWorley {
+                    min_radius,
+                    max_radius,
+                },
+            );
+            let mut inside = 0;
+            for y in 0..40 {
+                for x in 0..40 {
+                    let point = grid.get(Vec2::new(x as f32, y as f32) * 0.27);
+                    if blobs.get(point).adapt::<f32>() < 0.99 {
+                        inside += 1;
+                    }
+                }
+            }
+            inside
+        };
+        let small = coverage(0.05, 0.15);
+        let varied = coverage(0.05, 0.45);
+        let large = coverage(0.35, 0.45);
+        assert!(small < varied && varied < large);
+    }
+
+    #[test]
+    fn smooth_voronoi_sharpens_to_nearest() {
+        let grid = GridNoise::new_frequency(1.0);
+        let nearest = Voronoi::<2, VariableRadiusWorley>::new(
+            1.0,
+            8,
+            VariableRadiusWorley {
+                min_radius: 1.0,
+                max_radius: 1.0,
+            },
+        );
+        let error = |falloff: f32| {
+            let smooth = Voronoi::<2, SmoothVoronoi>::new(1.0, 8, SmoothVoronoi { falloff });
+            let mut largest = 0f32;
+            for y in 0..30 {
+                for x in 0..30 {
+                    let point = grid.get(Vec2::new(x as f32, y as f32) * 0.31);
+                    let difference = smooth.get(point.clone()).adapt::<f32>()
+                        - nearest.get(point).adapt::<f32>();
+                    assert!(difference > -1e-5);
+                    largest = largest.max(difference);
+                }
+            }
+            largest
+        };
+        let soft = error(4.0);
+        let sharp = error(200.0);
+        assert!(sharp < soft);
+        assert!(sharp < 0.02);
+    }
+
+    #[test]
+    fn cell_uvs_are_in_bounds() {
+        let grid = GridNoise::new_frequency(1.0);
+        let atlas_size = UVec2::new(4, 3);
+        let exact = Voronoi::<2, CellUvAtlas>::new(1.0, 11, CellUvAtlas { atlas_size });
+        let approx = Voronoi::<2, CellUvAtlas, true>::new(0.5, 11, CellUvAtlas { atlas_size });
+        let mut tiles = Vec::new();
+        for y in 0..40 {
+            for x in 0..40 {
+                let point = grid.get(Vec2::new(x as f32, y as f32) * 0.23);
+                for result in [exact.get(point.clone()), approx.get(point)] {
+                    assert!(result.value.cmpge(Vec2::ZERO).all());
+                    assert!(result.value.cmple(Vec2::ONE).all());
+                    assert!(result.meta.cmplt(atlas_size).all());
+                    tiles.push(result.meta);
+                }
+            }
+        }
+        assert!(tiles.iter().any(|tile| *tile != tiles[0]));
+    }
+
+    #[test]
+    fn wrapped_worley_tiles() {
+        let grid = WrappingGridNoise::new_tiling(2.0, UVec2::new(4, 3));
+        let worley =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>>::new_default(1.0, 6);
+        let approx =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>, true>::new_default(
+                1.0, 6,
+            );
+        let size = Vec2::new(8.0, 6.0);
+        for i in 0..=20 {
+            let t = i as f32 / 20.0;
+            for (a, b) in [
+                (Vec2::new(0.0, t * size.y), Vec2::new(size.x, t * size.y)),
+                (Vec2::new(t * size.x, 0.0), Vec2::new(t * size.x, size.y)),
+            ] {
+                for noise in [&worley as &dyn NoiseOp<_, Output = UNorm>, &approx] {
+                    let a = noise.get(grid.get(a)).adapt::<f32>();
+                    let b = noise.get(grid.get(b)).adapt::<f32>();
+                    assert!((a - b).abs() < 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn chebyshev_is_unorm() {
+        let grid = GridNoise::new_period(2.5);
+        let worley =
+            Voronoi::<2, Worley<ChebyshevDistance, worley_mode::Nearest>>::new_default(1.0, 4);
+        let approx =
+            Voronoi::<2, Worley<ChebyshevDistance, worley_mode::Nearest>, true>::new_default(
+                1.0, 4,
+            );
+        let cellular = Voronoi::<2, Cellular<ChebyshevDistance>>::new_default(1.0, 4);
+        for y in -20..20 {
+            for x in -20..20 {
+                let point = grid.get(Vec2::new(x as f32, y as f32) * 0.7);
+                for value in [worley.get(point.clone()), approx.get(point.clone())] {
+                    let value = value.adapt::<f32>();
+                    assert!((0.0..=1.0).contains(&value));
+                }
+                let nearest = cellular.get(point.clone()).value;
+                assert!(nearest.offset.abs().max_element() <= 1.5);
+            }
+        }
+    }
+
+    #[test]
+    fn minkowski_matches_euclidean() {
+        let grid = GridNoise::new_period(3.0);
+        let euclidean =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>>::new_default(0.8, 6);
+        let minkowski = Voronoi::<2, MinkowskiWorley<worley_mode::Nearest>>::new(
+            0.8,
+            6,
+            MinkowskiWorley::new(2.0),
+        );
+        for y in -20..20 {
+            for x in -20..20 {
+                let point = grid.get(Vec2::new(x as f32, y as f32) * 0.7);
+                let expected = euclidean.get(point.clone()).adapt::<f32>();
+                let value = minkowski.get(point).adapt::<f32>();
+                assert!((expected - value).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn nth_nearest_matches_fixed_modes() {
+        let grid = GridNoise::new_period(2.0);
+        let nearest =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::Nearest>>::new_default(1.0, 2);
+        let next =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::NextNearest>>::new_default(1.0, 2);
+        let nth_0 =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::NthNearest<0>>>::new_default(
+                1.0, 2,
+            );
+        let nth_1 =
+            Voronoi::<2, Worley<EuclideanDistance, worley_mode::NthNearest<1>>>::new_default(
+                1.0, 2,
+            );
+        for y in -20..20 {
+            for x in -20..20 {
+                let point = grid.get(Vec2::new(x as f32, y as f32) * 0.7);
+                assert_eq!(nearest.get(point.clone()), nth_0.get(point.clone()));
+                assert_eq!(next.get(point.clone()), nth_1.get(point));
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn worley_round_trips() {
+        let worley = Worley::<EuclideanDistance, worley_mode::NthNearest<2>>::shrunk_by(0.75);
+        let json = serde_json::to_string(&worley).unwrap();
+        let back: Worley<EuclideanDistance, worley_mode::NthNearest<2>> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            back.expected_length_multiplier,
+            worley.expected_length_multiplier
+        );
+    }
+
+    #[cfg(feature = "reflect")]
+    #[test]
+    fn worley_modes_reflect() {
+        use bevy_reflect::Struct;
+
+        fn mode_name<M: Default>() -> String
+        where
+            Worley<EuclideanDistance, M>: Struct,
+        {
+            let worley = Worley::<EuclideanDistance, M>::shrunk_by(0.5);
+            let mode = worley.field("mode").unwrap();
+            mode.reflect_short_type_path().to_string()
+        }
+        assert_eq!(mode_name::<worley_mode::Nearest>(), "Nearest");
+        assert_eq!(mode_name::<worley_mode::NextNearest>(), "NextNearest");
+        assert_eq!(mode_name::<worley_mode::NthNearest<2>>(), "NthNearest<2>");
+        assert_eq!(mode_name::<worley_mode::Difference>(), "Difference");
+        assert_eq!(mode_name::<worley_mode::Average>(), "Average");
+        assert_eq!(mode_name::<worley_mode::Product>(), "Product");
+        assert_eq!(mode_name::<worley_mode::Ratio>(), "Ratio");
+    }
+
+    #[test]
+    fn cells_have_plausible_neighbors() {
+        let grid = GridNoise::new_frequency(1.0);
+        let voronoi = Voronoi::<2, CellNeighbors>::full(7, CellNeighbors);
+        let mut total = 0;
+        for y in -10..10 {
+            for x in -10..10 {
+                let input = Vec2::new(x as f32, y as f32) * 0.73;
+                let neighbors = voronoi.get(grid.get(input));
+                let count = neighbors.0.iter().flatten().count();
+                assert!((1..=8).contains(&count));
+                total += count;
+            }
+        }
+        // some neighbors may be outside the surroundings, but most cells have 3 to 8.
+        let mean = total as f32 / 400.0;
+        assert!((3.0..=8.0).contains(&mean));
+
+        // an unnudged grid is made of squares, which share edges with the 4 cells beside them.
+        let square = Voronoi::<2, CellNeighbors>::new(0.0, 7, CellNeighbors);
+        let neighbors = square.get(grid.get(Vec2::new(0.2, 0.3)));
+        assert_eq!(neighbors.0.iter().flatten().count(), 4);
+    }
+
+    #[test]
+    fn scales_repeat_per_cell() {
+        let grid = GridNoise::new_frequency(1.0);
+        let voronoi = Voronoi::<2, Scales>::full_default(3);
+        let square = Voronoi::<2, Scales>::new_default(0.0, 3);
+        for y in -10..10 {
+            for x in -10..10 {
+                let input = Vec2::new(x as f32, y as f32) * 0.61;
+                let value = voronoi.get(grid.get(input)).adapt::<f32>();
+                assert!((0.0..=1.0).contains(&value));
+
+                // without nudging, every cell has the same scale.
+                let value = square.get(grid.get(input)).adapt::<f32>();
+                let shifted = square.get(grid.get(input + Vec2::new(3.0, -2.0)));
+                assert!((value - shifted.adapt::<f32>()).abs() < 1e-5);
+            }
+        }
+        // without nudging, the points are on the grid's corners.
+        let center = square.get(grid.get(Vec2::new(1.0, 1.0))).adapt::<f32>();
+        assert!(center < 1e-5);
+        // the lower scale covers the one above it, even where that one's point is nearer.
+        let overlap = square.get(grid.get(Vec2::new(1.0, 1.6))).adapt::<f32>();
+        assert!((overlap - 0.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cell_gradients_are_lines() {
+        let grid = GridNoise::new_frequency(1.0);
+        let gradient = Voronoi::<2, CellGradient>::full(21, CellGradient);
+        let cells = Voronoi::<2, CellValue>::full(21, CellValue);
+        let mut checked = 0;
+        for y in -10..10 {
+            for x in -10..10 {
+                let input = Vec2::new(x as f32, y as f32) * 0.83;
+                let samples = [input, input + Vec2::X * 0.1, input + Vec2::Y * 0.1];
+                let cell = cells.get(grid.get(input));
+                if samples[1..]
+                    .iter()
+                    .any(|sample| cells.get(grid.get(*sample)) != cell)
+                {
+                    continue;
+                }
+                // samples in the same cell are on the line between its two colors.
+                let [a, b, c] = samples.map(|sample| gradient.get(grid.get(sample)));
+                assert!((b - a).cross(c - a).length() < 1e-5);
+                assert!(
+                    [a, b, c]
+                        .iter()
+                        .all(|color| color.min_element() >= 0.0 && color.max_element() <= 1.0)
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked > 100);
+    }
+
+    #[test]
+    fn thinner_mortar_covers_less() {
+        let grid = GridNoise::new_frequency(1.0);
+        let coverage = |width, inset| {
+            let mortar = Voronoi::<2, Mortar>::full(13, Mortar { width, inset });
+            let mut covered = 0;
+            for y in 0..40 {
+                for x in 0..40 {
+                    let input = Vec2::new(x as f32, y as f32) * 0.27;
+                    let value = mortar.get(grid.get(input)).adapt::<f32>();
+                    assert!(!(1e-6..=1.0 - 1e-6).contains(&value));
+                    covered += (value > 0.5) as u32;
+                }
+            }
+            covered as f32 / 1600.0
+        };
+        let [wide, thin, none] = [0.2, 0.05, 0.0].map(|width| coverage(width, 0.0));
+        assert!(wide > thin);
+        assert!(thin > 0.0);
+        assert_eq!(none, 0.0);
+
+        // insets only ever widen the mortar, by up to the full inset.
+        let inset = coverage(0.1, 1.0);
+        assert!(inset > coverage(0.1, 0.0));
+        assert!(inset < coverage(0.2, 0.0));
+    }
+}
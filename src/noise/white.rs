@@ -13,9 +13,14 @@ use bevy_math::{
     UVec2,
     UVec3,
     UVec4,
+    Vec2,
 };
 
-use super::NoiseOp;
+use super::{
+    NoiseOp,
+    NoiseType,
+    norm::UNorm,
+};
 
 /// This creates a white noise implementation
 macro_rules! impl_white {
@@ -136,6 +141,108 @@ impl_white!(usize, WhiteUsize, 104_395_303,);
 #[cfg(target_pointer_width = "64")]
 impl_white!(usize, WhiteUsize, 982_451_653,);
 
+/// A [`NoiseOp`] that hashes a coordinate into a [`UNorm`], repeating exactly every `size`.
+/// This is handy as a small tileable texture for stochastic masking.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TileableHash {
+    /// The size of the tile. Zero is treated as one.
+    pub size: UVec2,
+    /// The seed of the hash.
+    pub seed: u32,
+}
+
+impl NoiseOp<UVec2> for TileableHash {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: UVec2) -> Self::Output {
+        let wrapped = input % self.size.max(UVec2::ONE);
+        White32(self.seed).get(wrapped).adapt()
+    }
+}
+
+/// A [`NoiseOp`] that produces interleaved gradient noise, a per pixel pattern that is popular for
+/// dithering in real time rendering, especially with temporal anti-aliasing.
+/// Each frame shifts the pattern, so it changes over time while staying well distributed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InterleavedGradient {
+    /// The frame to produce the pattern for. The pattern repeats every 64 frames.
+    pub frame: u32,
+}
+
+impl NoiseOp<UVec2> for InterleavedGradient {
+    type Output = UNorm;
+
+    #[inline]
+    fn get(&self, input: UVec2) -> Self::Output {
+        let shift = 5.588238 * (self.frame % 64) as f32;
+        let x = input.x as f32 + shift;
+        let y = input.y as f32 + shift;
+        UNorm::new_clamped((52.982918 * (0.06711056 * x + 0.00583715 * y).fract()).fract())
+    }
+}
+
+/// Maps a sample index to a point in the unit square, for supersampling or other Monte Carlo
+/// sampling. The points come from the base 2 and 3 Halton sequence, so any run of consecutive
+/// indices is spread evenly over the square. The whole sequence is shifted (wrapping around) by an
+/// amount based on the `seed`, so different seeds give different, but equally even, points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleJitter {
+    /// The seed that shifts the sequence.
+    pub seed: u32,
+}
+
+/// Computes the radical inverse of `index` in `base`, the `index`th element of a van der Corput
+/// sequence.
+#[inline]
+fn radical_inverse(mut index: u32, base: u32) -> f32 {
+    let inv_base = 1.0 / base as f32;
+    let mut scale = inv_base;
+    let mut result = 0.0;
+    while index > 0 {
+        result += (index % base) as f32 * scale;
+        index /= base;
+        scale *= inv_base;
+    }
+    result
+}
+
+impl NoiseOp<u32> for SampleJitter {
+    type Output = Vec2;
+
+    #[inline]
+    fn get(&self, input: u32) -> Self::Output {
+        let shift = Vec2::new(
+            White32(self.seed).get(0).adapt::<UNorm>().adapt::<f32>(),
+            White32(self.seed).get(1).adapt::<UNorm>().adapt::<f32>(),
+        );
+        let point = Vec2::new(radical_inverse(input, 2), radical_inverse(input, 3)) + shift;
+        // `fract` can round up to 1.0 for values just under an integer.
+        point.fract().min(Vec2::splat(1.0 - f32::EPSILON))
+    }
+}
+
+/// A [`NoiseOp`] that deterministically shuffles an array of `N` values with a seed, via a
+/// Fisher–Yates shuffle. The same seed always gives the same order, which is useful for
+/// randomizing the order octaves or features are processed in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Shuffle<const N: usize>;
+
+impl<T: NoiseType, const N: usize> NoiseOp<([T; N], u32)> for Shuffle<N> {
+    type Output = [T; N];
+
+    #[inline]
+    fn get(&self, input: ([T; N], u32)) -> Self::Output {
+        let (mut values, seed) = input;
+        let rng = White32(seed);
+        for index in (1..N).rev() {
+            let other = rng.get(index as u32) as usize % (index + 1);
+            values.swap(index, other);
+        }
+        values
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,4 +259,64 @@ mod tests {
         let _tmp = rng.get(UVec3::new(1, 2, 3));
         let _tmp = rng.get(UVec4::new(1, 2, 3, 4));
     }
+
+    #[test]
+    fn tileable_hash_tiles() {
+        let hash = TileableHash {
+            size: UVec2::new(16, 7),
+            seed: 3,
+        };
+        for coord in [UVec2::ZERO, UVec2::new(5, 6), UVec2::new(100, 31)] {
+            assert_eq!(hash.get(coord), hash.get(coord + hash.size));
+        }
+    }
+
+    #[test]
+    fn interleaved_gradient_is_uniform() {
+        for frame in [0, 1, 17] {
+            let ign = InterleavedGradient { frame };
+            let mut buckets = [0u32; 4];
+            for y in 0..32 {
+                for x in 0..32 {
+                    let pixel = UVec2::new(x, y);
+                    let value = ign.get(pixel);
+                    assert_eq!(value, InterleavedGradient { frame }.get(pixel));
+                    buckets[((value.adapt::<f32>() * 4.0) as usize).min(3)] += 1;
+                }
+            }
+            for bucket in buckets {
+                assert!((200..=312).contains(&bucket));
+            }
+        }
+    }
+
+    #[test]
+    fn sample_jitter_covers_square() {
+        let jitter = SampleJitter { seed: 42 };
+        let mut buckets = [0u32; 16];
+        for index in 0..256 {
+            let point = jitter.get(index);
+            assert_eq!(point, SampleJitter { seed: 42 }.get(index));
+            assert!(point.cmpge(Vec2::ZERO).all() && point.cmplt(Vec2::ONE).all());
+            let cell = (point * 4.0).as_uvec2().min(UVec2::splat(3));
+            buckets[(cell.x + cell.y * 4) as usize] += 1;
+        }
+        for bucket in buckets {
+            assert!((12..=20).contains(&bucket));
+        }
+        assert_ne!(jitter.get(0), SampleJitter { seed: 43 }.get(0));
+    }
+
+    #[test]
+    fn shuffle_is_reproducible_permutation() {
+        let values: [u32; 16] = core::array::from_fn(|i| i as u32);
+        let shuffled = Shuffle.get((values, 7));
+        assert_eq!(shuffled, Shuffle.get((values, 7)));
+        assert_ne!(shuffled, values);
+        assert_ne!(shuffled, Shuffle.get((values, 8)));
+
+        let mut sorted = shuffled;
+        sorted.sort();
+        assert_eq!(sorted, values);
+    }
 }
@@ -3,6 +3,7 @@
 use rand::{
     Error,
     RngCore,
+    SeedableRng,
 };
 use rand_core::impls;
 
@@ -14,10 +15,29 @@ use crate::noise::{
     },
 };
 
+/// A [`NoiseOp`] that can be used to advance the state of a [`NoiseRng`] or [`NoiseRng64`] by many
+/// steps at once.
+pub trait NoiseJump<T>: NoiseOp<T, Output = T> {
+    /// Advances `state` by `steps` steps.
+    ///
+    /// By default, this just steps `steps` times, which is O(`steps`). Implementors that can jump
+    /// directly, like linear congruential generators, should override this.
+    #[inline]
+    fn jump(&self, mut state: T, steps: u64) -> T {
+        for _ in 0..steps {
+            state = self.get(state);
+        }
+        state
+    }
+}
+
+impl NoiseJump<u32> for White32 {}
+impl NoiseJump<u64> for White64 {}
+
 /// A 64-bit version of [`NoiseRng`]. Use this when you are working primarily with 64-bit numbers.
 /// You may use this to generate seeds, etc. In general, [`NoiseRng`] is a better pick.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct NoiseRng64<N: NoiseOp<u64, Output = u64>>(N, u64);
+pub struct NoiseRng64<N>(N, u64);
 
 impl<N: NoiseOp<u64, Output = u64>> RngCore for NoiseRng64<N> {
     #[inline]
@@ -51,6 +71,14 @@ impl<N: NoiseOp<u64, Output = u64>> NoiseRng64<N> {
     }
 }
 
+impl<N: NoiseJump<u64>> NoiseRng64<N> {
+    /// Advances this rng as if `steps` values were generated. See [`NoiseJump::jump`] for the cost.
+    #[inline]
+    pub fn skip(&mut self, steps: u64) {
+        self.1 = self.0.jump(self.1, steps);
+    }
+}
+
 impl NoiseRng64<White64> {
     /// Constructs a new rng with this seed.
     pub fn new_seed(seed: u128) -> Self {
@@ -58,6 +86,16 @@ impl NoiseRng64<White64> {
     }
 }
 
+impl SeedableRng for NoiseRng64<White64> {
+    /// The little endian bytes of the seed for [`new_seed`](Self::new_seed).
+    type Seed = [u8; 16];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new_seed(u128::from_le_bytes(seed))
+    }
+}
+
 impl<N: NoiseOp<u64, Output = u64> + Clone> NoiseRng64<N> {
     /// Creates a new version of Self from this one.
     pub fn break_off(&mut self) -> Self {
@@ -66,6 +104,141 @@ impl<N: NoiseOp<u64, Output = u64> + Clone> NoiseRng64<N> {
     }
 }
 
+impl NoiseRng64<Pcg32> {
+    /// Constructs a new rng with this seed. The upper 64 bits pick the [`Pcg32`] stream. This
+    /// generates the same values as [`Pcg32::new`] with the same seed and stream.
+    pub fn new_seed(seed: u128) -> Self {
+        let pcg = Pcg32::new(seed as u64, (seed >> 64) as u64);
+        Self(pcg, pcg.state)
+    }
+
+    /// Advances this rng as if `steps` values were generated. This is O(log `steps`).
+    #[inline]
+    pub fn skip(&mut self, steps: u64) {
+        // each value is two steps of the underlying generator.
+        self.1 = self.0.advance(self.1, steps.wrapping_mul(2));
+    }
+}
+
+impl RngCore for NoiseRng64<Pcg32> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let high = Pcg32::output(self.1);
+        self.1 = self.0.step(self.1);
+        let low = Pcg32::output(self.1);
+        self.1 = self.0.step(self.1);
+        ((high as u64) << 32) | low as u64
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        #[allow(clippy::unit_arg)]
+        Ok(self.fill_bytes(dest))
+    }
+}
+
+/// A [PCG](https://www.pcg-random.org) random number generator, using the standard XSH-RR output.
+/// This gives much higher quality streams than re-hashing the last output, so it is a good pick
+/// for generating seeds.
+///
+/// As an [`RngCore`], this is the standard 32 bit PCG. It can also drive a [`NoiseRng64<Pcg32>`],
+/// which keeps the raw PCG state and only permutes the values it generates, so it generates the
+/// same values as [`Pcg32::next_u64`] and can [`skip`](NoiseRng64::skip) ahead in
+/// O(log `steps`).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// The multiplier of the underlying linear congruential generator.
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+    /// Constructs a new [`Pcg32`] from this seed on this stream. Different streams give unrelated
+    /// sequences, even for the same seed.
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut result = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        result.state = result.step(result.state).wrapping_add(seed);
+        result.state = result.step(result.state);
+        result
+    }
+
+    /// Advances the `state` by one step.
+    #[inline]
+    fn step(&self, state: u64) -> u64 {
+        state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.increment)
+    }
+
+    /// Advances the `state` by `delta` steps in O(log `delta`), by composing the steps of the
+    /// linear congruential generator like Brown's "Random Number Generation with Arbitrary
+    /// Strides".
+    #[inline]
+    fn advance(&self, state: u64, mut delta: u64) -> u64 {
+        let mut acc_mult = 1u64;
+        let mut acc_plus = 0u64;
+        let mut cur_mult = Self::MULTIPLIER;
+        let mut cur_plus = self.increment;
+        while delta > 0 {
+            if delta & 1 == 1 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta >>= 1;
+        }
+        acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+    }
+
+    /// The XSH-RR output function, which permutes the `state` into 32 bits.
+    #[inline]
+    fn output(state: u64) -> u32 {
+        let xor_shifted = (((state >> 18) ^ state) >> 27) as u32;
+        xor_shifted.rotate_right((state >> 59) as u32)
+    }
+}
+
+impl RngCore for Pcg32 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = self.step(old);
+        Self::output(old)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        #[allow(clippy::unit_arg)]
+        Ok(self.fill_bytes(dest))
+    }
+}
+
 /// A rng that uses a noise function as its randomizer. This operates on 32 bit noise, so it is a
 /// good default RNG.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -103,11 +276,35 @@ impl<N: NoiseOp<u32, Output = u32>> NoiseRng<N> {
     }
 }
 
+impl<N: NoiseJump<u32>> NoiseRng<N> {
+    /// Advances this rng as if `steps` values were generated. See [`NoiseJump::jump`] for the cost.
+    #[inline]
+    pub fn skip(&mut self, steps: u64) {
+        self.1 = self.0.jump(self.1, steps);
+    }
+}
+
 impl NoiseRng<White32> {
     /// Constructs a new rng with this seed.
     pub fn new_seed(seed: u64) -> Self {
         Self::new_with(White32(seed as u32), (seed >> 32) as u32)
     }
+
+    /// Gets the seed that [`new_seed`](Self::new_seed) would need to recreate this rng in its
+    /// current state.
+    pub fn current_seed(&self) -> u64 {
+        ((self.1 as u64) << 32) | self.0.0 as u64
+    }
+}
+
+impl SeedableRng for NoiseRng<White32> {
+    /// The little endian bytes of the seed for [`new_seed`](Self::new_seed).
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new_seed(u64::from_le_bytes(seed))
+    }
 }
 
 impl<N: NoiseOp<u32, Output = u32> + Clone> NoiseRng<N> {
@@ -117,3 +314,126 @@ impl<N: NoiseOp<u32, Output = u32> + Clone> NoiseRng<N> {
         Self(self.0.clone(), start.rotate_left(12)) // rotation just to desync the two generators
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the chi-squared statistic of the byte frequencies of `bytes`.
+    fn byte_chi_squared(bytes: &[u8]) -> f32 {
+        let mut counts = [0u32; 256];
+        for byte in bytes {
+            counts[*byte as usize] += 1;
+        }
+        let expected = bytes.len() as f32 / 256.0;
+        counts
+            .iter()
+            .map(|count| (*count as f32 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn seedable_rngs_are_reproducible() {
+        let mut a = NoiseRng::<White32>::seed_from_u64(99);
+        let mut b = NoiseRng::<White32>::seed_from_u64(99);
+        for _ in 0..8 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+        let mut a = NoiseRng64::<White64>::seed_from_u64(99);
+        let mut b = NoiseRng64::<White64>::seed_from_u64(99);
+        for _ in 0..8 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        let seed = 0x0123_4567_89ab_cdef;
+        assert_eq!(
+            NoiseRng::<White32>::from_seed(u64::to_le_bytes(seed)),
+            NoiseRng::new_seed(seed)
+        );
+        let mut from_rng = NoiseRng::<White32>::from_rng(NoiseRng::new_seed(seed)).unwrap();
+        assert_eq!(
+            from_rng.next_u32(),
+            NoiseRng::<White32>::from_rng(NoiseRng::new_seed(seed))
+                .unwrap()
+                .next_u32()
+        );
+    }
+
+    #[test]
+    fn skip_matches_stepping() {
+        let mut stepped = NoiseRng::new_seed(17);
+        let mut skipped = stepped;
+        for _ in 0..5 {
+            stepped.next_u32();
+        }
+        skipped.skip(5);
+        assert_eq!(stepped, skipped);
+
+        let mut stepped = NoiseRng64::<Pcg32>::new_seed(17);
+        let mut skipped = stepped;
+        for _ in 0..5 {
+            stepped.next_u64();
+        }
+        skipped.skip(5);
+        assert_eq!(stepped, skipped);
+    }
+
+    #[test]
+    fn pcg_matches_reference() {
+        // the first outputs of the reference implementation's demo, seeded with 42 on stream 54.
+        let mut pcg = Pcg32::new(42, 54);
+        assert_eq!(pcg.next_u32(), 0xa15c02b7);
+        assert_eq!(pcg.next_u32(), 0x7b47f409);
+
+        let mut pcg = Pcg32::new(42, 54);
+        let mut rng = NoiseRng64::<Pcg32>::new_seed((54 << 64) | 42);
+        assert_eq!(rng.next_u64(), 0xa15c02b7_7b47f409);
+        pcg.next_u64();
+        for _ in 0..100 {
+            assert_eq!(rng.next_u64(), pcg.next_u64());
+        }
+    }
+
+    #[test]
+    fn pcg_skips_in_log_time() {
+        let mut stepped = NoiseRng64::<Pcg32>::new_seed(17);
+        let mut skipped = stepped;
+        for _ in 0..1 << 20 {
+            stepped.next_u64();
+        }
+        skipped.skip(1 << 20);
+        assert_eq!(stepped, skipped);
+        // a full period of the underlying generator is 2^63 steps here, so this wraps around.
+        skipped.skip(1 << 63);
+        assert_eq!(stepped, skipped);
+    }
+
+    #[test]
+    fn pcg_bytes_are_uniform() {
+        let mut pcg = Pcg32::new(42, 54);
+        let mut bytes = vec![0u8; 10_000 * 4];
+        pcg.fill_bytes(&mut bytes);
+        // 255 degrees of freedom, so this should be near 255.
+        assert!(byte_chi_squared(&bytes) < 330.0);
+
+        let mut rng = NoiseRng64::<Pcg32>::new_seed(42);
+        let bytes = (0..10_000)
+            .flat_map(|_| rng.next_u64().to_le_bytes())
+            .collect::<Vec<_>>();
+        assert!(byte_chi_squared(&bytes) < 330.0);
+    }
+
+    #[test]
+    fn pcg_seeds_diverge() {
+        let mut a = Pcg32::new(1, 0);
+        let mut b = Pcg32::new(2, 0);
+        assert_ne!(a.next_u32(), b.next_u32());
+        assert_ne!(Pcg32::new(1, 0).next_u32(), Pcg32::new(1, 1).next_u32());
+
+        let mut a = NoiseRng64::<Pcg32>::new_seed(1);
+        let mut b = NoiseRng64::<Pcg32>::new_seed(2);
+        a.next_u64();
+        b.next_u64();
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+}
@@ -736,6 +736,17 @@ impl<T: Copy> Corners3d<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spatial::interpolating::Linear;
+
+    #[test]
+    fn trilinear_interpolation() {
+        // f(x, y, z) = x + 2y + 4z at each corner, which trilinear interpolation reproduces.
+        let corners = Corners3d([0.0f32, 4.0, 2.0, 6.0, 1.0, 5.0, 3.0, 7.0]);
+        let at = |x, y, z| corners.interpolate_3d::<f32, f32>(Axies3d([x, y, z]), &Linear);
+        assert_eq!(at(0.0, 0.0, 0.0), 0.0);
+        assert_eq!(at(1.0, 1.0, 1.0), 7.0);
+        assert_eq!(at(0.5, 0.25, 0.75), 4.0);
+    }
 
     #[test]
     fn test_sides_and_axies() {
@@ -104,6 +104,17 @@ impl<T: Sub<T, Output = T> + Div<T, Output = T> + Copy + Lerpable<T>> LerpableIn
     }
 }
 
+/// Performs an interpolation between the two `extents` of a line to the coordinate in `by`
+/// according to the `curve`. This is the 1d equivalent of `interpolate_2d` and friends.
+#[inline(always)]
+pub fn mix_1d<T: Lerpable<L> + Copy, I: Copy, L>(
+    extents: &[T; 2],
+    by: [I; 1],
+    curve: &impl MixerFxn<I, L>,
+) -> T {
+    T::lerp_dirty(extents[0], extents[1], curve.mix(by[0]))
+}
+
 /// A linear mixing function.
 /// Note that complex derivatives using this will not be continuous.
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
@@ -113,6 +124,11 @@ pub struct Linear;
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Cubic;
 
+/// A Quintic mixing function, Perlin's smootherstep. Unlike [`Cubic`], its second derivative is
+/// also continuous.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Quintic;
+
 /// Allows implementing curves easily
 macro_rules! impl_curves {
     ($t:ty) => {
@@ -140,6 +156,18 @@ macro_rules! impl_curves {
                 6.0 * (x - x * x)
             }
         }
+
+        impl MixerFxn<$t, $t> for Quintic {
+            #[inline]
+            fn mix(&self, x: $t) -> $t {
+                x * x * x * (x * (x * 6.0 - 15.0) + 10.0)
+            }
+
+            #[inline]
+            fn derivative(&self, x: $t) -> $t {
+                30.0 * x * x * (x * (x - 2.0) + 1.0)
+            }
+        }
     };
 
     ($f:ty, $v:ty) => {
@@ -179,3 +207,18 @@ impl_curves!(f32, Vec4);
 impl_curves!(f64, DVec2);
 impl_curves!(f64, DVec3);
 impl_curves!(f64, DVec4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quintic_is_smootherstep() {
+        assert_eq!(MixerFxn::<f32, f32>::mix(&Quintic, 0.0), 0.0);
+        assert_eq!(MixerFxn::<f32, f32>::mix(&Quintic, 1.0), 1.0);
+        assert_eq!(MixerFxn::<f32, f32>::mix(&Quintic, 0.5), 0.5);
+        assert_eq!(MixerFxn::<f32, f32>::derivative(&Quintic, 0.5), 1.875);
+        assert_eq!(MixerFxn::<f64, f64>::derivative(&Quintic, 0.0), 0.0);
+        assert_eq!(MixerFxn::<f64, f64>::derivative(&Quintic, 1.0), 0.0);
+    }
+}
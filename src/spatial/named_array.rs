@@ -190,6 +190,17 @@ macro_rules! name_array {
             pub fn each_mut(&mut self) -> $c<&mut T> {
                 $c(self.0.each_mut())
             }
+
+            /// collects the items of `iter` into self, or returns `None` if `iter` has the wrong number of items.
+            #[inline]
+            pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Option<Self> {
+                let mut iter = iter.into_iter();
+                let values: [Option<T>; <$i as $crate::spatial::named_array::NamedArrayIndices>::LEN] = core::array::from_fn(|_| iter.next());
+                if iter.next().is_some() || values.iter().any(Option::is_none) {
+                    return None;
+                }
+                Some(Self(values.map(|value| value.unwrap())))
+            }
         }
 
         impl<T> From<[T; <$i as $crate::spatial::named_array::NamedArrayIndices>::LEN]> for $c<T> {
@@ -446,6 +446,17 @@ impl<T: Copy> Corners2d<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::spatial::interpolating::Linear;
+
+    #[test]
+    fn bilinear_interpolation() {
+        // f(x, y) = 1 + 2x + 3y + 4xy at each corner, which bilinear interpolation reproduces.
+        let corners = Corners2d([1.0f32, 4.0, 3.0, 10.0]);
+        let at = |x, y| corners.interpolate_2d::<f32, f32>(Axies2d([x, y]), &Linear);
+        assert_eq!(at(0.0, 0.0), 1.0);
+        assert_eq!(at(1.0, 1.0), 10.0);
+        assert_eq!(at(0.25, 0.5), 3.5);
+    }
 
     #[test]
     fn test_sides_and_axies() {
@@ -536,4 +547,12 @@ mod tests {
             assert_eq!(c, back);
         }
     }
+
+    #[test]
+    fn corners_from_iter() {
+        let corners = Corners2d::try_from_iter([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(corners, Some(Corners2d([1.0, 2.0, 3.0, 4.0])));
+        assert_eq!(Corners2d::<f32>::try_from_iter([1.0, 2.0, 3.0]), None);
+        assert_eq!(Corners2d::<f32>::try_from_iter([0.0; 5]), None);
+    }
 }
@@ -0,0 +1,7 @@
+//! Checks that mistakes in `noise_op!` are reported at the tokens that caused them.
+
+#[test]
+fn noise_op_errors() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,8 @@
+use noiz::noise::noise_op;
+
+noise_op! {
+    struct Broken for bevy_math::Vec2 -> bevy_math::Vec2 = 5
+    impl
+}
+
+fn main() {}
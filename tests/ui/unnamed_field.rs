@@ -0,0 +1,9 @@
+use noiz::noise::noise_op;
+
+noise_op! {
+    struct Broken for bevy_math::Vec2 -> bevy_math::Vec2 =
+    struct BrokenArgs { f32, }
+    impl
+}
+
+fn main() {}